@@ -0,0 +1,838 @@
+mod error;
+mod utility;
+
+use crate::error::{checker_error, checker_warning};
+use crate::parser::expression::Expression;
+use crate::parser::pattern::{Arm, Pattern};
+use crate::parser::statement::{Field, Method, Param, Statement};
+use crate::parser::types::Type;
+use crate::tokenizer::token::Token;
+use crate::tokenizer::token_type::TokenType;
+use error::*;
+use std::collections::{HashMap, HashSet};
+use utility::*;
+
+// What the checker knows about a name.
+enum Symbol {
+    Value {
+        type_: Option<Type>, // None when it can't be known before running.
+        annotation: Option<Type>,
+    },
+    Function {
+        params: Vec<Param>,
+        output: Option<Type>,
+        coroutine: bool,
+    },
+    Class {
+        fields: HashMap<String, Type>, // Annotated instance fields, including inherited ones.
+        statics: HashMap<String, Type>, // Annotated static fields.
+    },
+    Instance {
+        fields: HashMap<String, Type>,
+    },
+}
+
+// Find type errors without running the program, reporting all of them.
+pub struct Checker {
+    scopes: Vec<HashMap<String, Symbol>>,
+    assigned: HashSet<String>, // Unannotated variables with these names can change type.
+    functions: Vec<(Token, Option<Type>)>, // Enclosing functions and their return types.
+    classes: Vec<HashMap<String, Type>>, // Annotated fields of the enclosing classes.
+}
+
+impl Checker {
+    pub fn new() -> Checker {
+        Checker {
+            scopes: vec![HashMap::new()],
+            assigned: HashSet::new(),
+            functions: Vec::new(),
+            classes: Vec::new(),
+        }
+    }
+
+    pub fn check(&mut self, statements: &Vec<Statement>) {
+        self.assigned = assigned_names(statements);
+
+        for stmt in statements {
+            self.statement(stmt);
+        }
+    }
+
+    fn statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::Var {
+                identifier,
+                annotation,
+            } => self.var(identifier, annotation, None),
+            Statement::VarAssign {
+                identifier,
+                annotation,
+                expr,
+            }
+            | Statement::Const {
+                identifier,
+                annotation,
+                expr,
+            } => {
+                let t: Option<Type> = self.expression(expr);
+
+                // Like types, instances are only known for variables never assigned.
+                match (annotation, self.instance_fields(expr)) {
+                    (None, Some(fields)) if !self.assigned.contains(&identifier.lexeme) => {
+                        self.declare(identifier, Symbol::Instance { fields: fields })
+                    }
+                    _ => self.var(identifier, annotation, t),
+                }
+            }
+            Statement::Func {
+                id,
+                params,
+                output,
+                body,
+            } => self.func(id, params, output, body, false),
+            Statement::Coro {
+                id,
+                params,
+                output,
+                body,
+            } => self.func(id, params, output, body, true),
+            Statement::Enum { id, variants: _ } | Statement::Interface { id, signatures: _ } => {
+                self.declare_unknown(id)
+            }
+            Statement::Struct { id, fields } => {
+                self.class(false, id, &None, fields, &Vec::new());
+            }
+            Statement::Class {
+                singleton,
+                id,
+                superclass,
+                interfaces: _,
+                fields,
+                methods,
+                signals: _,
+            } => {
+                self.class(*singleton, id, superclass, fields, methods);
+            }
+            Statement::Import {
+                keyword: _,
+                path,
+                alias,
+            } => match alias {
+                Some(a) => self.declare_unknown(a),
+                None => self.declare_unknown(&path[path.len() - 1]),
+            },
+            Statement::FromImport {
+                keyword: _,
+                path: _,
+                names,
+            } => {
+                for (name, alias) in names {
+                    match alias {
+                        Some(a) => self.declare_unknown(a),
+                        None => self.declare_unknown(name),
+                    }
+                }
+            }
+            Statement::Public { declaration } => self.statement(declaration),
+            Statement::Print { expr } | Statement::Expr { expr } => {
+                self.expression(expr);
+            }
+            Statement::Emit {
+                keyword: _,
+                signal,
+                args,
+            } => {
+                self.expression(signal);
+
+                for arg in args {
+                    self.expression(arg);
+                }
+            }
+            Statement::Block { stmts, level: _ } => {
+                self.scopes.push(HashMap::new());
+
+                for s in stmts {
+                    self.statement(s);
+                }
+
+                self.scopes.pop();
+            }
+            Statement::If {
+                condition,
+                statement,
+            } => {
+                self.expression(condition);
+
+                match self.null_test(condition) {
+                    Some((TokenType::NotEqual, id, t)) => self.narrowed(statement, &id, t),
+                    _ => self.statement(statement),
+                }
+            }
+            Statement::While {
+                condition,
+                statement,
+            } => {
+                self.expression(condition);
+                self.statement(statement);
+            }
+            Statement::IfElse {
+                condition,
+                if_statement,
+                else_statement,
+            } => {
+                self.expression(condition);
+
+                match self.null_test(condition) {
+                    Some((TokenType::NotEqual, id, t)) => {
+                        self.narrowed(if_statement, &id, t);
+                        self.statement(else_statement);
+                    }
+                    Some((TokenType::EqualEqual, id, t)) => {
+                        self.statement(if_statement);
+                        self.narrowed(else_statement, &id, t);
+                    }
+                    _ => {
+                        self.statement(if_statement);
+                        self.statement(else_statement);
+                    }
+                }
+            }
+            Statement::For {
+                id,
+                iterable,
+                statement,
+            } => {
+                self.expression(iterable);
+                self.scopes.push(HashMap::new());
+                self.declare_unknown(id);
+                self.statement(statement);
+                self.scopes.pop();
+            }
+            Statement::Loop { statement } => self.statement(statement),
+            Statement::Match {
+                token: _,
+                expr,
+                arms,
+            } => {
+                self.expression(expr);
+
+                for arm in arms {
+                    self.arm(arm);
+                }
+            }
+            Statement::Break | Statement::Continue => (),
+            Statement::Return { expr } => self.return_(expr),
+            Statement::Yield { keyword: _, expr } => match expr {
+                Some(e) => {
+                    self.expression(e);
+                }
+                None => (),
+            },
+            Statement::Await {
+                keyword: _,
+                expr,
+                id,
+            } => {
+                self.expression(expr);
+
+                match id {
+                    Some(i) => self.declare_unknown(i),
+                    None => (),
+                }
+            }
+        }
+    }
+
+    fn var(&mut self, id: &Token, annotation: &Option<Type>, t: Option<Type>) {
+        match (annotation, &t) {
+            (Some(a), Some(t)) if !self.fits(id.line, a, t) => {
+                checker_error(id.line, variable_type_mismatch(&id.lexeme, a, t));
+            }
+            _ => (),
+        }
+
+        // Without annotation, only variables that are never assigned keep their type.
+        let type_: Option<Type> = match annotation {
+            Some(a) => Some(a.clone()),
+            None if self.assigned.contains(&id.lexeme) => None,
+            None => t,
+        };
+
+        self.declare(
+            id,
+            Symbol::Value {
+                type_: type_,
+                annotation: annotation.clone(),
+            },
+        );
+    }
+
+    fn func(
+        &mut self,
+        id: &Token,
+        params: &Vec<Param>,
+        output: &Option<Type>,
+        body: &Statement,
+        coroutine: bool,
+    ) {
+        match self.assigned.contains(&id.lexeme) {
+            true => self.declare_unknown(id),
+            false => self.declare(
+                id,
+                Symbol::Function {
+                    params: params.clone(),
+                    output: output.clone(),
+                    coroutine: coroutine,
+                },
+            ),
+        }
+
+        self.function_body(id, params, output, body);
+    }
+
+    fn function_body(
+        &mut self,
+        id: &Token,
+        params: &Vec<Param>,
+        output: &Option<Type>,
+        body: &Statement,
+    ) {
+        self.scopes.push(HashMap::new());
+        self.functions.push((id.clone(), output.clone()));
+
+        for param in params {
+            let type_: Option<Type> = param.annotation.clone();
+            let symbol: Symbol = Symbol::Value {
+                type_: type_.clone(),
+                annotation: type_,
+            };
+            self.declare(&param.id, symbol);
+        }
+
+        self.statement(body);
+
+        self.functions.pop();
+        self.scopes.pop();
+    }
+
+    fn class(
+        &mut self,
+        singleton: bool,
+        id: &Token,
+        superclass: &Option<Token>,
+        fields: &Vec<Field>,
+        methods: &Vec<Method>,
+    ) {
+        let mut instance: HashMap<String, Type> = match superclass {
+            Some(s) => match self.lookup(&s.lexeme) {
+                Some(Symbol::Class { fields, statics: _ }) => fields.clone(),
+                _ => HashMap::new(),
+            },
+            None => HashMap::new(),
+        };
+        let mut statics: HashMap<String, Type> = HashMap::new();
+
+        for field in fields {
+            let types: &mut HashMap<String, Type> = match field.static_ {
+                true => &mut statics,
+                false => &mut instance,
+            };
+
+            match &field.annotation {
+                Some(a) => {
+                    types.insert(field.id.lexeme.clone(), a.clone());
+                }
+                None => {
+                    types.remove(&field.id.lexeme);
+                }
+            }
+        }
+
+        // Singletons are used through their only instance.
+        match (self.assigned.contains(&id.lexeme), singleton) {
+            (true, _) => self.declare_unknown(id),
+            (false, true) => self.declare(
+                id,
+                Symbol::Instance {
+                    fields: instance.clone(),
+                },
+            ),
+            (false, false) => self.declare(
+                id,
+                Symbol::Class {
+                    fields: instance.clone(),
+                    statics: statics,
+                },
+            ),
+        }
+
+        self.classes.push(instance);
+        self.fields(fields);
+        self.methods(methods);
+        self.classes.pop();
+    }
+
+    fn fields(&mut self, fields: &Vec<Field>) {
+        for field in fields {
+            let t: Option<Type> = match &field.default {
+                Some(d) => self.expression(d),
+                None => None,
+            };
+
+            match (&field.annotation, &t) {
+                (Some(a), Some(t)) if !self.fits(field.id.line, a, t) => {
+                    checker_error(field.id.line, field_type_mismatch(&field.id.lexeme, a, t));
+                }
+                _ => (),
+            }
+
+            // Accessors see the stored value through the field name.
+            self.scopes.push(HashMap::new());
+            self.declare_unknown(&field.id);
+
+            match &field.getter {
+                Some(g) => self.function_body(&field.id, &Vec::new(), &None, g),
+                None => (),
+            }
+
+            match &field.setter {
+                Some((p, s)) => {
+                    let param: Param = Param {
+                        id: p.clone(),
+                        annotation: None,
+                    };
+                    self.function_body(&field.id, &vec![param], &None, s);
+                }
+                None => (),
+            }
+
+            self.scopes.pop();
+        }
+    }
+
+    fn methods(&mut self, methods: &Vec<Method>) {
+        for method in methods {
+            self.function_body(&method.id, &method.params, &method.output, &method.body);
+        }
+    }
+
+    fn arm(&mut self, arm: &Arm) {
+        self.scopes.push(HashMap::new());
+
+        match &arm.pattern {
+            Pattern::Value { expr } => {
+                self.expression(expr);
+            }
+            Pattern::Range { start, op: _, end } => {
+                self.expression(start);
+                self.expression(end);
+            }
+            _ => (),
+        }
+
+        for id in bindings(&arm.pattern) {
+            self.declare_unknown(id);
+        }
+
+        match &arm.guard {
+            Some(g) => {
+                self.expression(g);
+            }
+            None => (),
+        }
+
+        self.statement(&arm.statement);
+        self.scopes.pop();
+    }
+
+    fn return_(&mut self, expr: &Option<Box<Expression>>) {
+        let t: Option<Type> = match expr {
+            Some(e) => self.expression(e),
+            None => Some(Type::Void),
+        };
+
+        let (id, output) = match self.functions.last() {
+            Some((id, Some(o))) => (id, o),
+            _ => return,
+        };
+
+        let line: usize = match expr {
+            Some(e) => line(e),
+            None => id.line,
+        };
+
+        match &t {
+            Some(t) if !self.fits(line, output, t) => {
+                checker_error(line, return_type_mismatch(&id.lexeme, output, t));
+            }
+            _ => (),
+        }
+    }
+
+    // Type of the expression, if it can be known before running.
+    fn expression(&mut self, expr: &Expression) -> Option<Type> {
+        match expr {
+            Expression::Literal { token } => literal_type(&token.token_type),
+            Expression::Variable { id } => match self.lookup(&id.lexeme) {
+                Some(Symbol::Value {
+                    type_,
+                    annotation: _,
+                }) => type_.clone(),
+                _ => None,
+            },
+            Expression::Grouping { expr } => self.expression(expr),
+            Expression::Unary { op, right } => self.unary(op, right),
+            Expression::Binary { left, op, right } => {
+                let l: Option<Type> = self.expression(left);
+                let r: Option<Type> = self.expression(right);
+                self.binary(op, l, r)
+            }
+            Expression::Logical { left, op, right } => {
+                let l: Option<Type> = self.expression(left);
+                let r: Option<Type> = self.expression(right);
+
+                match op.token_type {
+                    TokenType::QuestionMarkQuestionMark => coalesce(l, r),
+
+                    // Gives back one of the sides.
+                    _ => match l == r {
+                        true => l,
+                        false => None,
+                    },
+                }
+            }
+            Expression::Range {
+                start,
+                op: _,
+                end,
+                step,
+            } => {
+                self.expression(start);
+                self.expression(end);
+
+                match step {
+                    Some(s) => {
+                        self.expression(s);
+                    }
+                    None => (),
+                }
+
+                None
+            }
+            Expression::Assignment { id, op, right } => {
+                self.assignment(id, op, right);
+                Some(Type::Void)
+            }
+            Expression::Set {
+                object,
+                name,
+                op,
+                right,
+            } => {
+                self.expression(object);
+                self.set(object, name, op, right);
+                Some(Type::Void)
+            }
+            Expression::Call {
+                callee,
+                paren,
+                args,
+                named,
+            } => {
+                let mut types: Vec<Option<Type>> = Vec::new();
+
+                for arg in args {
+                    types.push(self.expression(arg));
+                }
+
+                for (_, arg) in named {
+                    self.expression(arg);
+                }
+
+                match &**callee {
+                    Expression::Variable { id } => self.call(id, paren, &types),
+                    Expression::SafeGet { object, name: _ } => {
+                        self.expression(object);
+                        Some(Type::Nullable(Box::new(Type::Unknown)))
+                    }
+                    callee => {
+                        self.expression(callee);
+                        None
+                    }
+                }
+            }
+            Expression::Get { object, name: _ } => {
+                self.expression(object);
+                None
+            }
+            // Null when the object is null, otherwise a member known when running.
+            Expression::SafeGet { object, name: _ } => {
+                self.expression(object);
+                Some(Type::Nullable(Box::new(Type::Unknown)))
+            }
+            Expression::Self_ { keyword: _ }
+            | Expression::Super {
+                keyword: _,
+                method: _,
+            } => None,
+        }
+    }
+
+    fn unary(&mut self, op: &Token, right: &Expression) -> Option<Type> {
+        let t: Option<Type> = match (&op.token_type, self.expression(right)) {
+            (TokenType::Not, t) | (TokenType::Resume, t) => t,
+            (_, Some(t @ Type::Nullable(_))) => {
+                checker_warning(op.line, possibly_null(&op.lexeme));
+                non_null(t)
+            }
+            (_, t) => t,
+        };
+
+        let result: Result<Option<Type>, ()> = match (&op.token_type, &t) {
+            (TokenType::Not, _) => Ok(Some(Type::Boolean)),
+            (TokenType::Resume, _) | (_, None) => Ok(None),
+            (TokenType::Minus, Some(Type::Integer)) | (TokenType::Minus, Some(Type::Floating)) => {
+                Ok(t.clone())
+            }
+            (TokenType::ExclamationMark, Some(Type::Integer)) => Ok(t.clone()),
+            _ => Err(()),
+        };
+
+        match (result, &t) {
+            (Ok(t), _) => t,
+            (Err(_), Some(t)) => {
+                checker_error(op.line, unary_unsupported(&op.lexeme, t));
+                None
+            }
+            (Err(_), None) => None,
+        }
+    }
+
+    fn binary(&mut self, op: &Token, left: Option<Type>, right: Option<Type>) -> Option<Type> {
+        // Comparing with null is how values are tested.
+        let comparison: bool = match op.token_type {
+            TokenType::EqualEqual | TokenType::NotEqual | TokenType::Is => true,
+            _ => false,
+        };
+
+        match (&left, &right) {
+            (Some(Type::Nullable(_)), _) | (_, Some(Type::Nullable(_))) if !comparison => {
+                checker_warning(op.line, possibly_null(&op.lexeme));
+            }
+            _ => (),
+        }
+
+        let left: Option<Type> = left.and_then(non_null);
+        let right: Option<Type> = right.and_then(non_null);
+
+        match (&left, &right) {
+            (Some(l), Some(r)) => match operation(&op.token_type, l, r) {
+                Ok(t) => Some(t),
+                Err(_) => {
+                    checker_error(op.line, binary_unsupported(&op.lexeme, l, r));
+                    None
+                }
+            },
+            _ => match op.token_type {
+                TokenType::EqualEqual | TokenType::NotEqual | TokenType::Is => Some(Type::Boolean),
+                _ => None,
+            },
+        }
+    }
+
+    fn assignment(&mut self, id: &Token, op: &Token, right: &Expression) {
+        let mut t: Option<Type> = self.expression(right);
+
+        let (current, annotation) = match self.lookup(&id.lexeme) {
+            Some(Symbol::Value { type_, annotation }) => (type_.clone(), annotation.clone()),
+            _ => (None, None),
+        };
+
+        if op.token_type != TokenType::Equal {
+            t = self.binary(op, current, t);
+        }
+
+        match (annotation, t) {
+            (Some(a), Some(t)) if !self.fits(id.line, &a, &t) => {
+                checker_error(id.line, variable_type_mismatch(&id.lexeme, &a, &t));
+            }
+            _ => (),
+        }
+    }
+
+    fn set(&mut self, object: &Expression, name: &Token, op: &Token, right: &Expression) {
+        let mut t: Option<Type> = self.expression(right);
+
+        let annotation: Type = match self.field_annotation(object, &name.lexeme) {
+            Some(a) => a,
+            None => return,
+        };
+
+        if op.token_type != TokenType::Equal {
+            t = self.binary(op, Some(annotation.clone()), t);
+        }
+
+        match t {
+            Some(t) if !self.fits(name.line, &annotation, &t) => {
+                checker_error(
+                    name.line,
+                    field_type_mismatch(&name.lexeme, &annotation, &t),
+                );
+            }
+            _ => (),
+        }
+    }
+
+    // Annotation of the field, when the class of the object is known.
+    fn field_annotation(&self, object: &Expression, name: &str) -> Option<Type> {
+        let fields: &HashMap<String, Type> = match object {
+            Expression::Self_ { keyword: _ } => self.classes.last()?,
+            Expression::Variable { id } => match self.lookup(&id.lexeme)? {
+                Symbol::Instance { fields } => fields,
+                Symbol::Class { fields: _, statics } => statics,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        fields.get(name).cloned()
+    }
+
+    // Annotated fields of the instance created by the expression, as in "Point()".
+    fn instance_fields(&self, expr: &Expression) -> Option<HashMap<String, Type>> {
+        let id: &Token = match expr {
+            Expression::Call {
+                callee,
+                paren: _,
+                args: _,
+                named: _,
+            } => match &**callee {
+                Expression::Variable { id } => id,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        match self.lookup(&id.lexeme)? {
+            Symbol::Class { fields, statics: _ } => Some(fields.clone()),
+            _ => None,
+        }
+    }
+
+    fn call(&mut self, id: &Token, paren: &Token, types: &Vec<Option<Type>>) -> Option<Type> {
+        let (params, output, coroutine) = match self.lookup(&id.lexeme) {
+            Some(Symbol::Function {
+                params,
+                output,
+                coroutine,
+            }) => (params.clone(), output.clone(), *coroutine),
+            _ => return None,
+        };
+
+        if params.len() != types.len() {
+            checker_error(
+                paren.line,
+                arity_mismatch(&id.lexeme, params.len(), types.len()),
+            );
+            return None;
+        }
+
+        for (param, t) in params.iter().zip(types) {
+            match (&param.annotation, t) {
+                (Some(a), Some(t)) if !self.fits(paren.line, a, t) => {
+                    checker_error(
+                        paren.line,
+                        parameter_type_mismatch(&id.lexeme, &param.id.lexeme, a, t),
+                    );
+                }
+                _ => (),
+            }
+        }
+
+        // Calling a coroutine only creates it.
+        match coroutine {
+            true => None,
+            false => output,
+        }
+    }
+
+    // False when the value can't be stored, warning when it only may be null.
+    fn fits(&self, line: usize, expected: &Type, given: &Type) -> bool {
+        match given {
+            Type::Nullable(g) if !accepts(expected, given) && accepts(expected, g) => {
+                checker_warning(line, possibly_null_value(expected, given));
+                true
+            }
+            _ => accepts(expected, given),
+        }
+    }
+
+    // Nullable variable compared to null, as in "x != null" or "x == null".
+    fn null_test(&self, condition: &Expression) -> Option<(TokenType, Token, Option<Type>)> {
+        let (left, op, right) = match condition {
+            Expression::Binary { left, op, right } => (left, op, right),
+            _ => return None,
+        };
+
+        let id: &Token = match (&**left, &**right) {
+            (Expression::Variable { id }, Expression::Literal { token })
+            | (Expression::Literal { token }, Expression::Variable { id })
+                if token.token_type == TokenType::Null =>
+            {
+                id
+            }
+            _ => return None,
+        };
+
+        match self.lookup(&id.lexeme) {
+            Some(Symbol::Value {
+                type_: Some(t @ Type::Nullable(_)),
+                annotation: _,
+            }) => Some((op.token_type.clone(), id.clone(), non_null(t.clone()))),
+            _ => None,
+        }
+    }
+
+    // Check the statement knowing that the variable isn't null.
+    fn narrowed(&mut self, stmt: &Statement, id: &Token, t: Option<Type>) {
+        let annotation: Option<Type> = match self.lookup(&id.lexeme) {
+            Some(Symbol::Value {
+                type_: _,
+                annotation,
+            }) => annotation.clone(),
+            _ => None,
+        };
+
+        self.scopes.push(HashMap::new());
+        self.declare(
+            id,
+            Symbol::Value {
+                type_: t,
+                annotation: annotation,
+            },
+        );
+        self.statement(stmt);
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, id: &Token, symbol: Symbol) {
+        match self.scopes.last_mut() {
+            Some(s) => {
+                s.insert(id.lexeme.clone(), symbol);
+            }
+            None => (),
+        }
+    }
+
+    fn declare_unknown(&mut self, id: &Token) {
+        let symbol: Symbol = Symbol::Value {
+            type_: None,
+            annotation: None,
+        };
+        self.declare(id, symbol);
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Symbol> {
+        self.scopes.iter().rev().find_map(|s| s.get(name))
+    }
+}
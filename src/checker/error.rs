@@ -0,0 +1,83 @@
+use crate::parser::types::Type;
+
+pub fn variable_type_mismatch(name: &str, expected: &Type, got: &Type) -> String {
+    format!(
+        "Variable '{}' expects {}, got: {}",
+        name,
+        expected.to_string(),
+        type_to_string(got)
+    )
+}
+
+pub fn parameter_type_mismatch(function: &str, name: &str, expected: &Type, got: &Type) -> String {
+    format!(
+        "Parameter '{}' of '{}' expects {}, got: {}",
+        name,
+        function,
+        expected.to_string(),
+        type_to_string(got)
+    )
+}
+
+pub fn return_type_mismatch(function: &str, expected: &Type, got: &Type) -> String {
+    format!(
+        "Function '{}' must return {}, got: {}",
+        function,
+        expected.to_string(),
+        type_to_string(got)
+    )
+}
+
+pub fn field_type_mismatch(name: &str, expected: &Type, got: &Type) -> String {
+    format!(
+        "Field '{}' expects {}, got: {}",
+        name,
+        expected.to_string(),
+        type_to_string(got)
+    )
+}
+
+pub fn arity_mismatch(name: &str, expected: usize, got: usize) -> String {
+    format!("'{}' expects {} arguments but got {}", name, expected, got)
+}
+
+pub fn unary_unsupported(op: &str, t: &Type) -> String {
+    format!("Unsupported operator '{}' for: {}", op, type_to_string(t))
+}
+
+pub fn binary_unsupported(op: &str, t1: &Type, t2: &Type) -> String {
+    format!(
+        "Unsupported operator '{}' for: {} and {}",
+        op,
+        type_to_string(t1),
+        type_to_string(t2)
+    )
+}
+
+pub fn possibly_null(op: &str) -> String {
+    format!("Operand of '{}' may be null", op)
+}
+
+pub fn possibly_null_value(expected: &Type, got: &Type) -> String {
+    match got {
+        Type::Nullable(t) if **t == Type::Unknown => {
+            format!(
+                "Expected {}, got a value which may be null",
+                expected.to_string()
+            )
+        }
+        t => format!(
+            "Expected {}, got: {} which may be null",
+            expected.to_string(),
+            t.to_string()
+        ),
+    }
+}
+
+// Values of type "void" are null.
+fn type_to_string(t: &Type) -> String {
+    match t {
+        Type::Void => "null".to_string(),
+        t => t.to_string(),
+    }
+}
@@ -0,0 +1,298 @@
+use crate::parser::expression::Expression;
+use crate::parser::pattern::Pattern;
+use crate::parser::statement::Statement;
+use crate::parser::types::Type;
+use crate::parser::utility::walk;
+use crate::tokenizer::token::Token;
+use crate::tokenizer::token_type::TokenType;
+use std::collections::HashSet;
+
+// Names assigned anywhere in the program, which can change their type.
+pub fn assigned_names(statements: &Vec<Statement>) -> HashSet<String> {
+    let mut names: HashSet<String> = HashSet::new();
+
+    for stmt in statements {
+        walk(stmt, &mut |s| {
+            for expr in expressions(s) {
+                assignments(expr, &mut names);
+            }
+        });
+    }
+
+    names
+}
+
+// Expressions directly inside the statement.
+fn expressions(stmt: &Statement) -> Vec<&Expression> {
+    match stmt {
+        Statement::VarAssign {
+            identifier: _,
+            annotation: _,
+            expr,
+        }
+        | Statement::Const {
+            identifier: _,
+            annotation: _,
+            expr,
+        }
+        | Statement::Print { expr }
+        | Statement::Expr { expr }
+        | Statement::Await {
+            keyword: _,
+            expr,
+            id: _,
+        } => vec![expr],
+        Statement::If {
+            condition,
+            statement: _,
+        }
+        | Statement::IfElse {
+            condition,
+            if_statement: _,
+            else_statement: _,
+        }
+        | Statement::While {
+            condition,
+            statement: _,
+        } => vec![condition],
+        Statement::For {
+            id: _,
+            iterable,
+            statement: _,
+        } => vec![iterable],
+        Statement::Match {
+            token: _,
+            expr,
+            arms,
+        } => {
+            let mut exprs: Vec<&Expression> = vec![expr];
+
+            for arm in arms {
+                exprs.extend(arm.guard.as_deref());
+            }
+
+            exprs
+        }
+        Statement::Return { expr } | Statement::Yield { keyword: _, expr } => {
+            expr.as_deref().into_iter().collect()
+        }
+        Statement::Emit {
+            keyword: _,
+            signal,
+            args,
+        } => {
+            let mut exprs: Vec<&Expression> = vec![signal];
+            exprs.extend(args.iter());
+            exprs
+        }
+        Statement::Struct { id: _, fields }
+        | Statement::Class {
+            singleton: _,
+            id: _,
+            superclass: _,
+            interfaces: _,
+            fields,
+            methods: _,
+            signals: _,
+        } => fields.iter().filter_map(|f| f.default.as_deref()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn assignments(expr: &Expression, names: &mut HashSet<String>) {
+    match expr {
+        Expression::Assignment { id, op: _, right } => {
+            names.insert(id.lexeme.clone());
+            assignments(right, names);
+        }
+        Expression::Set {
+            object,
+            name: _,
+            op: _,
+            right,
+        } => {
+            assignments(object, names);
+            assignments(right, names);
+        }
+        Expression::Range {
+            start,
+            op: _,
+            end,
+            step,
+        } => {
+            assignments(start, names);
+            assignments(end, names);
+
+            match step {
+                Some(s) => assignments(s, names),
+                None => (),
+            }
+        }
+        Expression::Logical { left, op: _, right } | Expression::Binary { left, op: _, right } => {
+            assignments(left, names);
+            assignments(right, names);
+        }
+        Expression::Unary { op: _, right } => assignments(right, names),
+        Expression::Call {
+            callee,
+            paren: _,
+            args,
+            named,
+        } => {
+            assignments(callee, names);
+
+            for arg in args {
+                assignments(arg, names);
+            }
+
+            for (_, arg) in named {
+                assignments(arg, names);
+            }
+        }
+        Expression::Get { object, name: _ } | Expression::SafeGet { object, name: _ } => {
+            assignments(object, names)
+        }
+        Expression::Grouping { expr } => assignments(expr, names),
+        _ => (),
+    }
+}
+
+// Variables declared by the pattern.
+pub fn bindings(pattern: &Pattern) -> Vec<&Token> {
+    match pattern {
+        Pattern::Binding { id } => vec![id],
+        Pattern::Variant {
+            path: _,
+            paren: _,
+            fields,
+        } => fields.iter().flat_map(bindings).collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Line of the first token in the expression.
+pub fn line(expr: &Expression) -> usize {
+    match expr {
+        Expression::Assignment {
+            id,
+            op: _,
+            right: _,
+        } => id.line,
+        Expression::Set {
+            object,
+            name: _,
+            op: _,
+            right: _,
+        } => line(object),
+        Expression::Range {
+            start,
+            op: _,
+            end: _,
+            step: _,
+        } => line(start),
+        Expression::Logical {
+            left,
+            op: _,
+            right: _,
+        }
+        | Expression::Binary {
+            left,
+            op: _,
+            right: _,
+        } => line(left),
+        Expression::Unary { op, right: _ } => op.line,
+        Expression::Call {
+            callee,
+            paren: _,
+            args: _,
+            named: _,
+        } => line(callee),
+        Expression::Get { object, name: _ } | Expression::SafeGet { object, name: _ } => {
+            line(object)
+        }
+        Expression::Self_ { keyword } | Expression::Super { keyword, method: _ } => keyword.line,
+        Expression::Grouping { expr } => line(expr),
+        Expression::Variable { id } => id.line,
+        Expression::Literal { token } => token.line,
+    }
+}
+
+pub fn literal_type(token_type: &TokenType) -> Option<Type> {
+    match token_type {
+        TokenType::Boolean(_) => Some(Type::Boolean),
+        TokenType::Integer(_) => Some(Type::Integer),
+        TokenType::Floating(_) => Some(Type::Floating),
+        TokenType::Character(_) => Some(Type::Character),
+        TokenType::String_(_) => Some(Type::String_),
+        TokenType::Null => Some(Type::Void),
+        _ => None,
+    }
+}
+
+// Whether a value of the given type can be stored where the expected type is.
+pub fn accepts(expected: &Type, given: &Type) -> bool {
+    match (expected, given) {
+        (_, Type::Unknown) => true,
+        (Type::Nullable(_), Type::Void) => true,
+        (Type::Nullable(e), Type::Nullable(g)) => accepts(e, g),
+        (Type::Nullable(e), g) => accepts(e, g),
+        (e, g) => e == g,
+    }
+}
+
+// Type of the value once it isn't null, if it can be known before running.
+pub fn non_null(t: Type) -> Option<Type> {
+    match t {
+        Type::Nullable(t) if *t == Type::Unknown => None,
+        Type::Nullable(t) => Some(*t),
+        t => Some(t),
+    }
+}
+
+// Type of "left ?? right".
+pub fn coalesce(left: Option<Type>, right: Option<Type>) -> Option<Type> {
+    match (left, right) {
+        (Some(Type::Void), r) => r,
+        (Some(Type::Nullable(l)), Some(Type::Void)) => Some(Type::Nullable(l)),
+        (Some(Type::Nullable(l)), Some(r)) if *l == r => Some(r),
+        (Some(Type::Nullable(l)), Some(r)) if Type::Nullable(l.clone()) == r => Some(r),
+        (Some(Type::Nullable(_)), _) => None,
+        (l, _) => l, // Never null, so right is never used.
+    }
+}
+
+// Type of the result, mirroring the operators of the interpreter.
+pub fn operation(op: &TokenType, left: &Type, right: &Type) -> Result<Type, ()> {
+    let numbers: Result<Type, ()> = match (left, right) {
+        (Type::Integer, Type::Integer) => Ok(Type::Integer),
+        (Type::Integer, Type::Floating)
+        | (Type::Floating, Type::Integer)
+        | (Type::Floating, Type::Floating) => Ok(Type::Floating),
+        _ => Err(()),
+    };
+
+    match op {
+        TokenType::Ampersand
+        | TokenType::Pipe
+        | TokenType::Caret
+        | TokenType::GreaterGreater
+        | TokenType::LessLess
+        | TokenType::AmpersandEqual
+        | TokenType::PipeEqual
+        | TokenType::CaretEqual
+        | TokenType::GreaterGreaterEqual
+        | TokenType::LessLessEqual => match (left, right) {
+            (Type::Integer, Type::Integer) => Ok(Type::Integer),
+            _ => Err(()),
+        },
+        TokenType::Greater | TokenType::Less | TokenType::GreaterEqual | TokenType::LessEqual => {
+            numbers.map(|_| Type::Boolean)
+        }
+        TokenType::EqualEqual | TokenType::NotEqual | TokenType::Is => Ok(Type::Boolean),
+        TokenType::Plus | TokenType::PlusEqual => match (left, right) {
+            (Type::String_, Type::String_) => Ok(Type::String_),
+            _ => numbers,
+        },
+        _ => numbers,
+    }
+}
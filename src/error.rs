@@ -20,6 +20,7 @@ pub enum ExitCode {
 
 pub static mut TOKENIZER_ERROR: bool = false;
 pub static mut PARSER_ERROR: bool = false;
+pub static mut CHECKER_ERROR: bool = false;
 pub static mut INTERPRETER_ERROR: bool = false;
 
 pub fn had_error() -> bool {
@@ -31,7 +32,7 @@ pub fn had_error() -> bool {
 
 pub fn code_error() -> ExitCode {
     unsafe {
-        if TOKENIZER_ERROR || PARSER_ERROR {
+        if TOKENIZER_ERROR || PARSER_ERROR || CHECKER_ERROR {
             ExitCode::DATAERR
         } else if INTERPRETER_ERROR {
             ExitCode::SOFTWARE
@@ -45,6 +46,7 @@ pub fn clear_errors() {
     unsafe {
         TOKENIZER_ERROR = false;
         PARSER_ERROR = false;
+        CHECKER_ERROR = false;
         INTERPRETER_ERROR = false;
     }
 }
@@ -59,6 +61,19 @@ pub fn parser_error(line: usize, message: String) {
     unsafe { PARSER_ERROR = true }
 }
 
+pub fn parser_warning(line: usize, message: String) {
+    println!("[line {}] Warning: {}", line, message);
+}
+
+pub fn checker_error(line: usize, message: String) {
+    println!("[line {}] Error: {}", line, message);
+    unsafe { CHECKER_ERROR = true }
+}
+
+pub fn checker_warning(line: usize, message: String) {
+    println!("[line {}] Warning: {}", line, message);
+}
+
 pub fn interpreter_error(line: usize, message: String) {
     println!("[line {}] Error: {}", line, message);
     unsafe { INTERPRETER_ERROR = true }
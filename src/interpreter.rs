@@ -1,139 +1,2295 @@
+mod class;
+mod collector;
 mod content;
+mod coroutine;
+mod enumeration;
 mod environment;
 mod error;
+mod flow;
+mod function;
+mod interface;
+mod iteration;
+mod module;
+mod native;
+mod range;
+mod scheduler;
+mod signal;
+mod singleton;
+mod stdlib;
 mod utility;
 
-use crate::error::interpreter_error;
+use crate::error::{code_error, interpreter_error, ExitCode};
 use crate::parser::expression::Expression;
-use crate::parser::statement::Statement;
+use crate::parser::pattern::{Arm, Pattern};
+use crate::parser::statement::{
+    path_to_string, EnumVariant, Field, Method, Param, Signature, Statement,
+};
+use crate::parser::types::Type;
+use crate::parser::utility::declared_id;
+use crate::parser::Parser;
 use crate::tokenizer::token::Token;
 use crate::tokenizer::token_type::TokenType;
+use crate::tokenizer::Tokenizer;
+use class::{Class, Instance, Kind, DESTROYED};
+use collector::{collect_cycles, dispose, release, release_all, COLLECTED};
 use content::Content;
+use coroutine::{Coroutine, Frame, State, Stop};
+use enumeration::{Enum, Variant};
 use environment::Environment;
 use error::*;
+use flow::Flow;
+use function::Function;
+use interface::Interface;
+use iteration::Iteration;
+use module::Module;
+use native::Native;
+use range::Range;
+use scheduler::Scheduler;
+use signal::Signal;
+use singleton::Singleton;
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::mem;
+use std::path::PathBuf;
+use std::rc::{Rc, Weak};
 use utility::*;
 
+// Nested function calls allowed before reporting a stack overflow.
+const MAX_DEPTH: usize = 1000;
+
 pub struct Interpreter {
-    environment: Environment,
+    environment: Rc<RefCell<Environment>>,
+    globals: Rc<RefCell<Environment>>,
+    contexts: Vec<Rc<Class>>, // Classes of the methods being executed.
+    accessors: Vec<(usize, String, Rc<RefCell<Environment>>)>, // Running accessors by instance and field.
+    depth: usize,                                              // Function calls being executed.
+    scheduler: Scheduler,
+    files: Vec<PathBuf>,      // Files being executed, the importing ones first.
+    modules: Vec<Rc<Module>>, // By order of loading.
+    includes: Vec<PathBuf>,   // Searched before the directory of the importing file.
+    libraries: Vec<PathBuf>,  // Searched after the directory of the importing file.
 }
 
-impl Interpreter {
-    pub fn new() -> Interpreter {
-        Interpreter {
-            environment: Environment::new(),
+impl Interpreter {
+    // Without a file, imports are resolved from the current directory.
+    pub fn new(file: Option<PathBuf>, includes: Vec<PathBuf>) -> Interpreter {
+        let globals: Rc<RefCell<Environment>> = Interpreter::globals();
+        let files: Vec<PathBuf> = match file.map(|f| f.canonicalize()) {
+            Some(Ok(f)) => vec![f],
+            _ => Vec::new(),
+        };
+        let libraries: Vec<PathBuf> = match env::var_os("LAI_PATH") {
+            Some(p) => env::split_paths(&p).collect(),
+            None => Vec::new(),
+        };
+
+        Interpreter {
+            environment: Rc::clone(&globals),
+            globals: globals,
+            contexts: Vec::new(),
+            accessors: Vec::new(),
+            depth: 0,
+            scheduler: Scheduler::new(),
+            files: files,
+            modules: Vec::new(),
+            includes: includes,
+            libraries: libraries,
+        }
+    }
+
+    // Each module gets its own global scope.
+    fn globals() -> Rc<RefCell<Environment>> {
+        let globals: Rc<RefCell<Environment>> = Rc::new(RefCell::new(Environment::new()));
+
+        for native in scheduler::natives() {
+            let id: Token = Token {
+                token_type: TokenType::Identifier(native.id.clone()),
+                lexeme: native.id.clone(),
+                line: 0,
+            };
+            globals
+                .borrow_mut()
+                .define(&id, Content::Native(Rc::new(native)));
+        }
+
+        globals
+    }
+
+    // The top-level block is run here, so its scope, the ones of the modules and the
+    // globals are released after the tasks even if leaked objects can still reach them.
+    pub fn interpret(&mut self, statements: Vec<Statement>) {
+        self.begin_scope();
+
+        let mut result: Result<(), ()> = Ok(());
+
+        for s in top_level(&statements) {
+            match self.execute_top_level(s) {
+                Ok(Flow::Next) => (),
+                Ok(_) => break,
+                Err(_) => {
+                    result = Err(());
+                    break;
+                }
+            }
+        }
+
+        // Like the rest of the program, tasks stop at the first error.
+        if result.and_then(|_| self.run_tasks()).is_err() {
+            self.scheduler.cancel();
+        }
+
+        let enclosing: Option<Rc<RefCell<Environment>>> =
+            self.environment.borrow().enclosing.clone();
+
+        match enclosing {
+            Some(e) => release_all(mem::replace(&mut self.environment, e)),
+            None => (),
+        }
+
+        // Modules are released after the files importing them.
+        for module in mem::take(&mut self.modules).iter().rev() {
+            release_all(Rc::clone(&module.environment));
+        }
+
+        // Singletons are global, so they go last.
+        release_all(Rc::clone(&self.globals));
+
+        collect_cycles();
+        let _ = self.destroy();
+    }
+
+    // Cycles between released instances are collected after each top-level statement.
+    fn execute_top_level(&mut self, stmt: &Statement) -> Result<Flow, ()> {
+        let flow: Result<Flow, ()> = self.execute(stmt);
+        collect_cycles();
+        self.destroy().and(flow)
+    }
+
+    // Analogue to evaluate() but for statements.
+    fn execute(&mut self, stmt: &Statement) -> Result<Flow, ()> {
+        let flow: Result<Flow, ()> = match stmt {
+            Statement::Var {
+                identifier,
+                annotation,
+            } => self.var(identifier, annotation),
+            Statement::VarAssign {
+                identifier,
+                annotation,
+                expr,
+            } => self.var_assign(identifier, annotation, expr),
+            Statement::If {
+                condition,
+                statement,
+            } => self.if_(condition, statement),
+            Statement::IfElse {
+                condition,
+                if_statement,
+                else_statement,
+            } => self.if_else(condition, if_statement, else_statement),
+            Statement::While {
+                condition,
+                statement,
+            } => self.while_(condition, statement),
+            Statement::For {
+                id,
+                iterable,
+                statement,
+            } => self.for_(id, iterable, statement),
+            Statement::Loop { statement } => self.loop_(statement),
+            Statement::Match { token, expr, arms } => self.match_(token, expr, arms),
+            Statement::Break => Ok(Flow::Break),
+            Statement::Continue => Ok(Flow::Continue),
+            Statement::Const {
+                identifier,
+                annotation,
+                expr,
+            } => self.const_(identifier, annotation, expr),
+            Statement::Enum { id, variants } => self.enum_(id, variants),
+            Statement::Struct { id, fields } => self.struct_(id, fields),
+            Statement::Class {
+                singleton,
+                id,
+                superclass,
+                interfaces,
+                fields,
+                methods,
+                signals,
+            } => self
+                .class(id, superclass, interfaces, fields, methods, signals)
+                .and_then(|c| self.declare_class(*singleton, id, c)),
+            Statement::Interface { id, signatures } => self.interface(id, signatures),
+            Statement::Func {
+                id,
+                params,
+                output,
+                body,
+            } => self.func(id, params, output, body, false),
+            Statement::Coro {
+                id,
+                params,
+                output,
+                body,
+            } => self.func(id, params, output, body, true),
+            Statement::Return { expr } => self.return_(expr),
+            Statement::Yield { keyword, expr: _ } => {
+                interpreter_error(keyword.line, YIELD_OUTSIDE_COROUTINE.to_string());
+                Err(())
+            }
+            Statement::Await {
+                keyword,
+                expr: _,
+                id: _,
+            } => {
+                interpreter_error(keyword.line, AWAIT_OUTSIDE_COROUTINE.to_string());
+                Err(())
+            }
+            Statement::Import {
+                keyword,
+                path,
+                alias,
+            } => self.import(keyword, path, alias),
+            Statement::FromImport {
+                keyword,
+                path,
+                names,
+            } => self.import_from(keyword, path, names),
+            Statement::Public { declaration } => self.execute(declaration),
+            Statement::Print { expr } => self.print(expr),
+            Statement::Emit {
+                keyword,
+                signal,
+                args,
+            } => self.emit(keyword, signal, args),
+            Statement::Block { stmts, level: _ } => self.block(stmts),
+            Statement::Expr { expr } => self.expression(expr),
+        };
+
+        // Objects released by the statement are destroyed before the next one runs.
+        self.destroy()?;
+
+        flow
+    }
+
+    // Typed variables start as null until something is assigned.
+    fn var(&mut self, id: &Token, annotation: &Option<Type>) -> Result<Flow, ()> {
+        self.environment.borrow().check_declaration(id)?;
+        self.environment
+            .borrow_mut()
+            .define_typed(id, Content::Null, annotation);
+        Ok(Flow::Next)
+    }
+
+    fn var_assign(
+        &mut self,
+        id: &Token,
+        annotation: &Option<Type>,
+        expr: &Expression,
+    ) -> Result<Flow, ()> {
+        let c: Content = self.evaluate(expr)?;
+
+        match mismatch(&c, annotation) {
+            Some(t) => {
+                interpreter_error(id.line, variable_type_mismatch(&id.lexeme, t, &c));
+                return Err(());
+            }
+            None => (),
+        }
+
+        self.environment.borrow().check_declaration(id)?;
+        self.environment
+            .borrow_mut()
+            .define_typed(id, c, annotation);
+        Ok(Flow::Next)
+    }
+
+    fn const_(
+        &mut self,
+        id: &Token,
+        annotation: &Option<Type>,
+        expr: &Expression,
+    ) -> Result<Flow, ()> {
+        let c: Content = self.evaluate(expr)?;
+
+        match mismatch(&c, annotation) {
+            Some(t) => {
+                interpreter_error(id.line, variable_type_mismatch(&id.lexeme, t, &c));
+                return Err(());
+            }
+            None => (),
+        }
+
+        self.environment.borrow().check_declaration(id)?;
+        self.environment.borrow_mut().define_constant(id, c);
+        Ok(Flow::Next)
+    }
+
+    fn enum_(&mut self, id: &Token, variants: &Vec<EnumVariant>) -> Result<Flow, ()> {
+        let enumeration: Enum = Enum {
+            id: id.clone(),
+            variants: variants.clone(),
+        };
+
+        self.environment.borrow().check_declaration(id)?;
+        self.environment
+            .borrow_mut()
+            .define(id, Content::Enum(Rc::new(enumeration)));
+        Ok(Flow::Next)
+    }
+
+    fn struct_(&mut self, id: &Token, fields: &Vec<Field>) -> Result<Flow, ()> {
+        let structure: Rc<Class> = Rc::new_cyclic(|owner: &Weak<Class>| {
+            let (getters, setters) = self.accessors(fields, &self.environment, owner);
+
+            Class {
+                id: id.clone(),
+                kind: Kind::Struct,
+                superclass: None,
+                interfaces: Vec::new(),
+                fields: fields.clone(),
+                methods: HashMap::new(),
+                statics: RefCell::new(HashMap::new()),
+                static_methods: HashMap::new(),
+                public: fields.iter().map(|f| f.id.lexeme.clone()).collect(), // Always visible.
+                getters: getters,
+                setters: setters,
+                signals: Vec::new(),
+                types: field_types(fields),
+                closure: Rc::clone(&self.environment),
+            }
+        });
+
+        self.environment.borrow().check_declaration(id)?;
+        self.environment
+            .borrow_mut()
+            .define(id, Content::Class(structure));
+        Ok(Flow::Next)
+    }
+
+    fn class(
+        &mut self,
+        id: &Token,
+        superclass: &Option<Token>,
+        interfaces: &Vec<Token>,
+        fields: &Vec<Field>,
+        methods: &Vec<Method>,
+        signals: &Vec<Signature>,
+    ) -> Result<Rc<Class>, ()> {
+        let superclass: Option<Rc<Class>> = match superclass {
+            Some(s) => match self.environment.borrow().get(s)? {
+                Content::Class(c) if c.kind == Kind::Class => Some(c),
+                c => {
+                    interpreter_error(s.line, superclass_invalid(&c));
+                    return Err(());
+                }
+            },
+            None => None,
+        };
+
+        let mut implemented: Vec<Rc<Interface>> = Vec::new();
+
+        for interface in interfaces {
+            match self.environment.borrow().get(interface)? {
+                Content::Interface(i) => implemented.push(i),
+                c => {
+                    interpreter_error(interface.line, interface_invalid(&c));
+                    return Err(());
+                }
+            }
+        }
+
+        let mut statics: HashMap<String, Content> = HashMap::new();
+
+        for field in fields.iter().filter(|f| f.static_) {
+            let c: Content = match &field.default {
+                Some(d) => self.evaluate(d)?,
+                None => Content::Null,
+            };
+
+            match (&field.default, mismatch(&c, &field.annotation)) {
+                (Some(_), Some(t)) => {
+                    interpreter_error(field.id.line, field_type_mismatch(&field.id.lexeme, t, &c));
+                    return Err(());
+                }
+                _ => (),
+            }
+
+            statics.insert(field.id.lexeme.clone(), c);
+        }
+
+        // Methods of subclasses can reach the superclass through "super".
+        let closure: Rc<RefCell<Environment>> = match &superclass {
+            Some(s) => {
+                let super_: Token = Token {
+                    token_type: TokenType::Super,
+                    lexeme: "super".to_string(),
+                    line: id.line,
+                };
+                let mut environment: Environment = Environment::from(&self.environment);
+                environment.define(&super_, Content::Class(Rc::clone(s)));
+                Rc::new(RefCell::new(environment))
+            }
+            None => Rc::clone(&self.environment),
+        };
+
+        let mut public: HashSet<String> = HashSet::new();
+
+        for field in fields.iter().filter(|f| f.public) {
+            public.insert(field.id.lexeme.clone());
+        }
+
+        let types: HashMap<String, Type> = field_types(fields);
+        let fields: Vec<Field> = fields.iter().filter(|f| !f.static_).cloned().collect();
+
+        let class: Rc<Class> = Rc::new_cyclic(|owner: &Weak<Class>| {
+            let mut functions: HashMap<String, Rc<Function>> = HashMap::new();
+            let mut static_functions: HashMap<String, Rc<Function>> = HashMap::new();
+
+            for method in methods {
+                let function: Function = Function {
+                    id: method.id.clone(),
+                    params: method.params.clone(),
+                    output: method.output.clone(),
+                    body: Rc::clone(&method.body),
+                    closure: Rc::clone(&closure),
+                    owner: Some(owner.clone()),
+                    bound: None,
+                    coroutine: method.coroutine,
+                };
+
+                if method.public {
+                    public.insert(method.id.lexeme.clone());
+                }
+
+                match method.static_ {
+                    true => static_functions.insert(method.id.lexeme.clone(), Rc::new(function)),
+                    false => functions.insert(method.id.lexeme.clone(), Rc::new(function)),
+                };
+            }
+
+            let (getters, setters) = self.accessors(&fields, &closure, owner);
+
+            Class {
+                id: id.clone(),
+                kind: Kind::Class,
+                superclass: superclass,
+                interfaces: implemented,
+                fields: fields,
+                methods: functions,
+                statics: RefCell::new(statics),
+                static_methods: static_functions,
+                public: public,
+                getters: getters,
+                setters: setters,
+                signals: signals.clone(),
+                types: types,
+                closure: Rc::clone(&self.environment),
+            }
+        });
+
+        self.check_interfaces(&class)?;
+        Ok(class)
+    }
+
+    // Singletons are global, no matter where they were declared.
+    fn declare_class(&mut self, singleton: bool, id: &Token, class: Rc<Class>) -> Result<Flow, ()> {
+        if !singleton {
+            self.environment.borrow().check_declaration(id)?;
+            self.environment
+                .borrow_mut()
+                .define(id, Content::Class(class));
+            return Ok(Flow::Next);
+        }
+
+        let singleton: Singleton = Singleton {
+            class: class,
+            instance: RefCell::new(None),
+            initializing: Cell::new(false),
+        };
+
+        self.globals.borrow().check_declaration(id)?;
+        self.globals
+            .borrow_mut()
+            .define(id, Content::Singleton(Rc::new(singleton)));
+        Ok(Flow::Next)
+    }
+
+    // Turn "get" and "set" blocks into functions, indexed by field name.
+    fn accessors(
+        &self,
+        fields: &Vec<Field>,
+        closure: &Rc<RefCell<Environment>>,
+        owner: &Weak<Class>,
+    ) -> (HashMap<String, Rc<Function>>, HashMap<String, Rc<Function>>) {
+        let mut getters: HashMap<String, Rc<Function>> = HashMap::new();
+        let mut setters: HashMap<String, Rc<Function>> = HashMap::new();
+
+        for field in fields {
+            match &field.getter {
+                Some(body) => {
+                    let getter: Function = Function {
+                        id: field.id.clone(),
+                        params: Vec::new(),
+                        output: None,
+                        body: Rc::clone(body),
+                        closure: Rc::clone(closure),
+                        owner: Some(owner.clone()),
+                        bound: None,
+                        coroutine: false,
+                    };
+
+                    getters.insert(field.id.lexeme.clone(), Rc::new(getter));
+                }
+                None => (),
+            }
+
+            match &field.setter {
+                Some((param, body)) => {
+                    let setter: Function = Function {
+                        id: field.id.clone(),
+                        params: vec![Param {
+                            id: param.clone(),
+                            annotation: None,
+                        }],
+                        output: None,
+                        body: Rc::clone(body),
+                        closure: Rc::clone(closure),
+                        owner: Some(owner.clone()),
+                        bound: None,
+                        coroutine: false,
+                    };
+
+                    setters.insert(field.id.lexeme.clone(), Rc::new(setter));
+                }
+                None => (),
+            }
+        }
+
+        (getters, setters)
+    }
+
+    // Report every interface method that the class doesn't honor.
+    fn check_interfaces(&self, class: &Class) -> Result<(), ()> {
+        let mut result: Result<(), ()> = Ok(());
+
+        for interface in &class.interfaces {
+            for signature in &interface.signatures {
+                let name: &str = &signature.id.lexeme;
+
+                let public: bool = match class.owner(name) {
+                    Some(o) => o.public.contains(name),
+                    None => false,
+                };
+
+                match class.find_method(name) {
+                    Some(_) if !public => {
+                        interpreter_error(
+                            class.id.line,
+                            interface_method_private(&class.id.lexeme, &interface.id.lexeme, name),
+                        );
+                        result = Err(());
+                    }
+                    Some(m) if m.arity() == signature.arity() => (),
+                    Some(m) => {
+                        interpreter_error(
+                            class.id.line,
+                            interface_arity_mismatch(
+                                &class.id.lexeme,
+                                &interface.id.lexeme,
+                                name,
+                                signature.arity(),
+                                m.arity(),
+                            ),
+                        );
+                        result = Err(());
+                    }
+                    None => {
+                        interpreter_error(
+                            class.id.line,
+                            interface_method_missing(&class.id.lexeme, &interface.id.lexeme, name),
+                        );
+                        result = Err(());
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    fn interface(&mut self, id: &Token, signatures: &Vec<Signature>) -> Result<Flow, ()> {
+        let interface: Interface = Interface {
+            id: id.clone(),
+            signatures: signatures.clone(),
+        };
+
+        self.environment.borrow().check_declaration(id)?;
+        self.environment
+            .borrow_mut()
+            .define(id, Content::Interface(Rc::new(interface)));
+        Ok(Flow::Next)
+    }
+
+    fn func(
+        &mut self,
+        id: &Token,
+        params: &Vec<Param>,
+        output: &Option<Type>,
+        body: &Rc<Statement>,
+        coroutine: bool,
+    ) -> Result<Flow, ()> {
+        let function: Function = Function {
+            id: id.clone(),
+            params: params.clone(),
+            output: output.clone(),
+            body: Rc::clone(body),
+            closure: Rc::clone(&self.environment),
+            owner: None,
+            bound: None,
+            coroutine: coroutine,
+        };
+
+        self.environment.borrow().check_declaration(id)?;
+        self.environment
+            .borrow_mut()
+            .define(id, Content::Function(Rc::new(function)));
+        Ok(Flow::Next)
+    }
+
+    fn import(
+        &mut self,
+        keyword: &Token,
+        path: &Vec<Token>,
+        alias: &Option<Token>,
+    ) -> Result<Flow, ()> {
+        let module: Rc<Module> = self.module(keyword, path)?;
+        let id: &Token = match alias {
+            Some(a) => a,
+            None => &path[path.len() - 1],
+        };
+
+        self.environment.borrow().check_declaration(id)?;
+        self.environment
+            .borrow_mut()
+            .define(id, Content::Module(module));
+
+        Ok(Flow::Next)
+    }
+
+    fn import_from(
+        &mut self,
+        keyword: &Token,
+        path: &Vec<Token>,
+        names: &Vec<(Token, Option<Token>)>,
+    ) -> Result<Flow, ()> {
+        let module: Rc<Module> = self.module(keyword, path)?;
+
+        for (name, alias) in names {
+            let content: Content = match module.get(&name.lexeme) {
+                Some(c) => c,
+                None => {
+                    interpreter_error(name.line, module_member_undefined(&module.id, &name.lexeme));
+                    return Err(());
+                }
+            };
+
+            let id: &Token = match alias {
+                Some(a) => a,
+                None => name,
+            };
+
+            self.environment.borrow().check_declaration(id)?;
+            self.environment.borrow_mut().define(id, content);
+        }
+
+        Ok(Flow::Next)
+    }
+
+    // Execute the module file the first time it is imported.
+    fn module(&mut self, keyword: &Token, path: &Vec<Token>) -> Result<Rc<Module>, ()> {
+        let id: String = path_to_string(path);
+        let directories: Vec<PathBuf> = self.search_path();
+        // The bundled modules are searched last.
+        let (file, bundled): (PathBuf, Option<&str>) = match resolve(&directories, path) {
+            Some(f) => (f, None),
+            None => match stdlib::find(&id) {
+                Some((f, code)) => (f, Some(code)),
+                None => {
+                    let mut directories: Vec<String> = directories
+                        .iter()
+                        .map(|d| d.display().to_string())
+                        .collect();
+                    directories.push(stdlib::STDLIB.to_string());
+                    interpreter_error(keyword.line, module_not_found(&id, &directories));
+                    return Err(());
+                }
+            },
+        };
+
+        if self.files.contains(&file) {
+            let mut chain: Vec<String> = self.files.iter().map(|f| self.display(f)).collect();
+            chain.push(self.display(&file));
+            interpreter_error(keyword.line, import_cycle(&chain));
+            return Err(());
+        }
+
+        match self.modules.iter().find(|m| m.file == file) {
+            Some(m) => return Ok(Rc::clone(m)),
+            None => (),
+        }
+
+        let code: String = match bundled {
+            Some(c) => c.to_string(),
+            None => match fs::read_to_string(&file) {
+                Ok(c) => c,
+                _ => {
+                    interpreter_error(keyword.line, module_unreadable(&id));
+                    return Err(());
+                }
+            },
+        };
+
+        let tokens: Vec<Token> = Tokenizer::new(code).tokenize();
+        let statements: Vec<Statement> = match code_error() {
+            ExitCode::DATAERR => Vec::new(),
+            _ => Parser::new(tokens).parse(),
+        };
+
+        match code_error() {
+            ExitCode::DATAERR => {
+                interpreter_error(keyword.line, module_invalid(&id));
+                return Err(());
+            }
+            _ => (),
+        }
+
+        let globals: Rc<RefCell<Environment>> = Interpreter::globals();
+        let environment: Rc<RefCell<Environment>> =
+            mem::replace(&mut self.environment, Rc::clone(&globals));
+        let enclosing: Rc<RefCell<Environment>> =
+            mem::replace(&mut self.globals, Rc::clone(&globals));
+        let contexts: Vec<Rc<Class>> = mem::take(&mut self.contexts);
+        self.files.push(file.clone());
+
+        let public: Result<HashSet<String>, ()> = self.execute_module(&statements);
+
+        self.files.pop();
+        self.contexts = contexts;
+        self.globals = enclosing;
+        self.environment = environment;
+
+        let public: HashSet<String> = match public {
+            Ok(p) => p,
+            Err(_) => {
+                interpreter_error(keyword.line, module_failed(&id));
+                return Err(());
+            }
+        };
+
+        let module: Rc<Module> = Rc::new(Module {
+            id: id,
+            file: file,
+            environment: globals,
+            public: public,
+        });
+        self.modules.push(Rc::clone(&module));
+
+        Ok(module)
+    }
+
+    // Top-level statements run directly in the module global scope. Gives back the
+    // names of the public declarations.
+    fn execute_module(&mut self, statements: &Vec<Statement>) -> Result<HashSet<String>, ()> {
+        let mut public: HashSet<String> = HashSet::new();
+
+        for s in top_level(statements) {
+            self.execute_top_level(s)?;
+
+            match s {
+                Statement::Public { declaration } => match declared_id(declaration) {
+                    Some(id) => {
+                        public.insert(id.lexeme.clone());
+                    }
+                    None => (),
+                },
+                _ => (),
+            }
+        }
+
+        Ok(public)
+    }
+
+    // Directories to look for modules, by order of priority.
+    fn search_path(&self) -> Vec<PathBuf> {
+        let mut directories: Vec<PathBuf> = self.includes.clone();
+
+        match self.files.last() {
+            Some(f) => directories.extend(f.parent().map(|p| p.to_path_buf())),
+            None => directories.extend(env::current_dir().ok()),
+        }
+
+        directories.extend(self.libraries.iter().cloned());
+        directories
+    }
+
+    // Paths shown relative to the main file.
+    fn display(&self, file: &PathBuf) -> String {
+        let root: Option<&std::path::Path> = self.files.first().and_then(|f| f.parent());
+
+        match root.and_then(|r| file.strip_prefix(r).ok()) {
+            Some(f) => f.display().to_string(),
+            None => file.display().to_string(),
+        }
+    }
+
+    fn if_(&mut self, condition: &Expression, statement: &Statement) -> Result<Flow, ()> {
+        if is_true(&self.evaluate(condition)?) {
+            self.execute(statement)
+        } else {
+            Ok(Flow::Next)
+        }
+    }
+
+    fn if_else(
+        &mut self,
+        condition: &Expression,
+        if_statement: &Statement,
+        else_statement: &Statement,
+    ) -> Result<Flow, ()> {
+        if is_true(&self.evaluate(condition)?) {
+            self.execute(if_statement)
+        } else {
+            self.execute(else_statement)
+        }
+    }
+
+    fn while_(&mut self, condition: &Expression, statement: &Statement) -> Result<Flow, ()> {
+        while is_true(&self.evaluate(condition)?) {
+            match self.execute(statement)? {
+                Flow::Break => break,
+                Flow::Continue | Flow::Next => (),
+                Flow::Return(c) => return Ok(Flow::Return(c)),
+            }
+        }
+
+        Ok(Flow::Next)
+    }
+
+    fn for_(
+        &mut self,
+        id: &Token,
+        iterable: &Expression,
+        statement: &Statement,
+    ) -> Result<Flow, ()> {
+        let c: Content = self.evaluate(iterable)?;
+        let mut iteration: Iteration = self.iteration(c, id)?;
+
+        while let Some(c) = self.next(&mut iteration, id)? {
+            // Each iteration gets a fresh loop variable.
+            self.begin_scope();
+            self.environment.borrow_mut().define(id, c);
+            let flow: Result<Flow, ()> = self.execute(statement);
+            self.end_scope();
+
+            match flow? {
+                Flow::Break => break,
+                Flow::Continue | Flow::Next => (),
+                Flow::Return(c) => return Ok(Flow::Return(c)),
+            }
+        }
+
+        Ok(Flow::Next)
+    }
+
+    // Instances are iterated through what their "iter" method returns.
+    fn iteration(&mut self, content: Content, token: &Token) -> Result<Iteration, ()> {
+        let iteration: Iteration = match content {
+            Content::Range(r) => Iteration::Range { range: r, n: 0 },
+            Content::String_(s) => Iteration::String_ {
+                characters: s.chars().collect(),
+                n: 0,
+            },
+            Content::Coroutine(c) => Iteration::Coroutine(c),
+            Content::Instance(i) if i.borrow().class.find_method("iter").is_some() => {
+                let iter: Token = Token {
+                    token_type: TokenType::Identifier("iter".to_string()),
+                    lexeme: "iter".to_string(),
+                    line: token.line,
+                };
+                let method: Content = self.method(&Content::Instance(i), &iter)?;
+
+                match self.call_content(method, Vec::new(), token)? {
+                    Content::Instance(i) => {
+                        interpreter_error(token.line, iterator_invalid(&Content::Instance(i)));
+                        return Err(());
+                    }
+                    c => self.iteration(c, token)?,
+                }
+            }
+            c => {
+                interpreter_error(token.line, not_iterable(&c));
+                return Err(());
+            }
+        };
+
+        Ok(iteration)
+    }
+
+    fn next(&mut self, iteration: &mut Iteration, token: &Token) -> Result<Option<Content>, ()> {
+        match iteration {
+            Iteration::Range { range, n } => {
+                *n += 1;
+                Ok(range.nth(*n - 1))
+            }
+            Iteration::String_ { characters, n } => {
+                *n += 1;
+                Ok(characters.get(*n - 1).map(|c| Content::Character(*c)))
+            }
+            Iteration::Coroutine(c) => {
+                if c.state.get() == State::Finished {
+                    return Ok(None);
+                }
+
+                // The value returned when finishing isn't part of the iteration.
+                let value: Content = self.resume(Content::Coroutine(Rc::clone(c)), token)?;
+
+                match c.state.get() {
+                    State::Finished => Ok(None),
+                    _ => Ok(Some(value)),
+                }
+            }
+        }
+    }
+
+    fn loop_(&mut self, statement: &Statement) -> Result<Flow, ()> {
+        loop {
+            match self.execute(statement)? {
+                Flow::Break => break,
+                Flow::Continue | Flow::Next => (),
+                Flow::Return(c) => return Ok(Flow::Return(c)),
+            }
+        }
+
+        Ok(Flow::Next)
+    }
+
+    fn return_(&mut self, expr: &Option<Box<Expression>>) -> Result<Flow, ()> {
+        let c: Content = match expr {
+            Some(e) => self.evaluate(e)?,
+            None => Content::Null,
+        };
+
+        Ok(Flow::Return(c))
+    }
+
+    fn match_(&mut self, token: &Token, expr: &Expression, arms: &Vec<Arm>) -> Result<Flow, ()> {
+        let c: Content = self.evaluate(expr)?;
+
+        for arm in arms {
+            // Bindings from the pattern only live inside the arm.
+            self.begin_scope();
+            let flow: Result<Option<Flow>, ()> = self.arm(&c, arm);
+            self.end_scope();
+
+            match flow? {
+                Some(f) => return Ok(f),
+                None => (),
+            }
+        }
+
+        interpreter_error(token.line, no_match(&c));
+        Err(())
+    }
+
+    fn arm(&mut self, c: &Content, arm: &Arm) -> Result<Option<Flow>, ()> {
+        if !self.is_arm(c, arm)? {
+            return Ok(None);
+        }
+
+        Ok(Some(self.execute(&arm.statement)?))
+    }
+
+    // Check the pattern and the guard of the arm.
+    fn is_arm(&mut self, c: &Content, arm: &Arm) -> Result<bool, ()> {
+        if !self.is_match(c, &arm.pattern)? {
+            return Ok(false);
+        }
+
+        match &arm.guard {
+            Some(g) => Ok(is_true(&self.evaluate(g)?)),
+            None => Ok(true),
+        }
+    }
+
+    fn is_match(&mut self, c: &Content, pattern: &Pattern) -> Result<bool, ()> {
+        match pattern {
+            Pattern::Wildcard => Ok(true),
+            Pattern::Binding { id } => {
+                self.environment.borrow_mut().define(id, c.clone());
+                Ok(true)
+            }
+            Pattern::Value { expr } => Ok(self.evaluate(expr)? == *c),
+            Pattern::Range { start, op, end } => match self.range(start, op, end, &None)? {
+                Content::Range(r) => Ok(r.contains(c)),
+                _ => Ok(false),
+            },
+            Pattern::Variant {
+                path,
+                paren,
+                fields,
+            } => {
+                let constructor: Variant = match self.evaluate(path)? {
+                    Content::Constructor(v) => v,
+                    other => {
+                        interpreter_error(paren.line, pattern_not_variant(&other));
+                        return Err(());
+                    }
+                };
+
+                if constructor.arity() != fields.len() {
+                    interpreter_error(
+                        paren.line,
+                        pattern_arity_mismatch(
+                            &constructor.to_string(),
+                            constructor.arity(),
+                            fields.len(),
+                        ),
+                    );
+                    return Err(());
+                }
+
+                let variant: &Variant = match c {
+                    Content::Variant(v) if v.is_same_variant(&constructor) => v,
+                    _ => return Ok(false),
+                };
+
+                // Destructure each value into its own pattern.
+                for (value, field) in variant.values.iter().zip(fields) {
+                    if !self.is_match(value, field)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+        }
+    }
+
+    fn print(&mut self, expr: &Expression) -> Result<Flow, ()> {
+        let c: Content = self.evaluate(expr)?;
+        let context: Option<&Class> = self.contexts.last().map(|c| &**c);
+        println!("{}", c.to_string_in(context));
+        Ok(Flow::Next)
+    }
+
+    fn emit(
+        &mut self,
+        keyword: &Token,
+        signal: &Expression,
+        args: &Vec<Expression>,
+    ) -> Result<Flow, ()> {
+        let signal: Rc<Signal> = match self.evaluate(signal)? {
+            Content::Signal(s) => s,
+            c => {
+                interpreter_error(keyword.line, not_signal(&c));
+                return Err(());
+            }
+        };
+        let mut arguments: Vec<Content> = Vec::new();
+
+        for arg in args {
+            arguments.push(self.evaluate(arg)?);
+        }
+
+        if arguments.len() != signal.arity() {
+            interpreter_error(
+                keyword.line,
+                arity_mismatch(&signal.id.lexeme, signal.arity(), arguments.len()),
+            );
+            return Err(());
+        }
+
+        for (param, arg) in signal.params.iter().zip(&arguments) {
+            match mismatch(arg, &param.annotation) {
+                Some(t) => {
+                    interpreter_error(
+                        keyword.line,
+                        parameter_type_mismatch(&signal.id.lexeme, &param.id.lexeme, t, arg),
+                    );
+                    return Err(());
+                }
+                None => (),
+            }
+        }
+
+        self.emit_signal(&signal, arguments, keyword)?;
+        Ok(Flow::Next)
+    }
+
+    fn emit_signal(
+        &mut self,
+        signal: &Signal,
+        arguments: Vec<Content>,
+        token: &Token,
+    ) -> Result<(), ()> {
+        // Awaiting coroutines receive the argument, when there is only one.
+        let value: Content = match arguments.len() {
+            1 => arguments[0].clone(),
+            _ => Content::Null,
+        };
+
+        for coroutine in signal.waiting.take() {
+            self.scheduler.ready.push_back((coroutine, value.clone()));
+        }
+
+        // Handlers connected or disconnected by a handler only count from the next emission.
+        let handlers: Vec<Content> = signal.handlers.borrow().clone();
+
+        for handler in handlers {
+            self.call_content(handler, arguments.clone(), token)?;
+        }
+
+        Ok(())
+    }
+
+    fn block(&mut self, stmts: &Vec<Rc<Statement>>) -> Result<Flow, ()> {
+        self.begin_scope();
+
+        let mut flow: Result<Flow, ()> = Ok(Flow::Next);
+
+        for stmt in stmts {
+            flow = self.execute(stmt);
+
+            match flow {
+                Ok(Flow::Next) => (),
+                _ => break, // Unwind "break", "continue" and errors.
+            }
+        }
+
+        self.end_scope();
+
+        flow
+    }
+
+    fn begin_scope(&mut self) {
+        let environment: Environment = Environment::from(&self.environment);
+        self.environment = Rc::new(RefCell::new(environment));
+    }
+
+    fn end_scope(&mut self) {
+        let enclosing: Option<Rc<RefCell<Environment>>> =
+            self.environment.borrow().enclosing.clone();
+
+        match enclosing {
+            Some(e) => release(mem::replace(&mut self.environment, e)),
+            None => (),
+        }
+    }
+
+    fn expression(&mut self, expr: &Expression) -> Result<Flow, ()> {
+        self.evaluate(expr)?;
+        Ok(Flow::Next)
+    }
+
+    // Analogue to execute() but for expressions.
+    fn evaluate(&mut self, expr: &Expression) -> Result<Content, ()> {
+        let c: Content = match expr {
+            Expression::Literal { token } => Content::from(token.token_type.clone())?,
+            Expression::Variable { id } => self.variable(id)?,
+            Expression::Call {
+                callee,
+                paren,
+                args,
+                named,
+            } => self.call(callee, paren, args, named)?,
+            Expression::Get { object, name } => self.get(object, name)?,
+            Expression::SafeGet { object, name } => self.safe_get(object, name)?,
+            Expression::Self_ { keyword } => self.environment.borrow().get(keyword)?,
+            Expression::Super { keyword, method } => self.super_(keyword, method)?,
+            Expression::Grouping { expr } => self.evaluate(expr)?,
+            Expression::Unary { op, right } => self.unary(op, right)?,
+            Expression::Binary { left, op, right } => self.binary(left, op, right)?,
+            Expression::Range {
+                start,
+                op,
+                end,
+                step,
+            } => self.range(start, op, end, step)?,
+            Expression::Logical { left, op, right } => self.logical(left, op, right)?,
+            Expression::Set {
+                object,
+                name,
+                op,
+                right,
+            } => self.set(object, name, op, right)?,
+            Expression::Assignment { id, op, right } => self.assignment(id, op, right)?,
+        };
+
+        Ok(c)
+    }
+
+    fn variable(&mut self, id: &Token) -> Result<Content, ()> {
+        let c: Content = self.environment.borrow().get(id)?;
+
+        match c {
+            Content::Singleton(s) => self.singleton(&s, id),
+            c => Ok(c),
+        }
+    }
+
+    // Create the singleton instance the first time it is used.
+    fn singleton(&mut self, singleton: &Singleton, id: &Token) -> Result<Content, ()> {
+        match &*singleton.instance.borrow() {
+            Some(i) => return Ok(i.clone()),
+            None => (),
+        }
+
+        if singleton.initializing.get() {
+            interpreter_error(id.line, singleton_recursive(&id.lexeme));
+            return Err(());
+        }
+
+        singleton.initializing.set(true);
+        let instance: Result<Content, ()> = self.call_class(&singleton.class, Vec::new(), id);
+        singleton.initializing.set(false);
+
+        let instance: Content = instance?;
+        *singleton.instance.borrow_mut() = Some(instance.clone());
+
+        Ok(instance)
+    }
+
+    fn call(
+        &mut self,
+        callee: &Expression,
+        paren: &Token,
+        args: &Vec<Expression>,
+        named: &Vec<(Token, Expression)>,
+    ) -> Result<Content, ()> {
+        let callee: Content = match callee {
+            Expression::Get { object, name } => {
+                let object: Content = self.evaluate(object)?;
+                self.method(&object, name)?
+            }
+            Expression::SafeGet { object, name } => match self.evaluate(object)? {
+                // Arguments are not evaluated.
+                Content::Null => return Ok(Content::Null),
+                object => self.method(&object, name)?,
+            },
+            _ => self.evaluate(callee)?,
+        };
+        let mut arguments: Vec<Content> = Vec::new();
+        let mut named_arguments: Vec<(Token, Content)> = Vec::new();
+
+        for arg in args {
+            arguments.push(self.evaluate(arg)?);
+        }
+
+        for (name, arg) in named {
+            named_arguments.push((name.clone(), self.evaluate(arg)?));
+        }
+
+        match (&callee, named_arguments.is_empty()) {
+            (Content::Class(c), _) if c.kind == Kind::Struct => {
+                return self.call_struct(c, arguments, named_arguments, paren)
+            }
+            (_, false) => {
+                interpreter_error(paren.line, named_arguments_unsupported(&callee));
+                return Err(());
+            }
+            _ => (),
+        }
+
+        self.call_content(callee, arguments, paren)
+    }
+
+    fn call_content(
+        &mut self,
+        callee: Content,
+        arguments: Vec<Content>,
+        paren: &Token,
+    ) -> Result<Content, ()> {
+        match callee {
+            Content::Function(f) => self.call_function(&f, arguments, paren),
+            Content::Native(n) => self.call_native(&n, arguments, paren),
+            Content::Constructor(v) => self.call_constructor(v, arguments, paren),
+            Content::Class(c) => self.call_class(&c, arguments, paren),
+            c => {
+                interpreter_error(paren.line, not_callable(&c));
+                Err(())
+            }
+        }
+    }
+
+    fn call_native(
+        &mut self,
+        native: &Native,
+        arguments: Vec<Content>,
+        paren: &Token,
+    ) -> Result<Content, ()> {
+        if arguments.len() != native.arity {
+            interpreter_error(
+                paren.line,
+                arity_mismatch(&native.id, native.arity, arguments.len()),
+            );
+            return Err(());
+        }
+
+        (native.function)(self, native, arguments, paren)
+    }
+
+    fn call_function(
+        &mut self,
+        function: &Function,
+        arguments: Vec<Content>,
+        paren: &Token,
+    ) -> Result<Content, ()> {
+        if arguments.len() != function.arity() {
+            interpreter_error(
+                paren.line,
+                arity_mismatch(&function.id.lexeme, function.arity(), arguments.len()),
+            );
+            return Err(());
+        }
+
+        for (param, arg) in function.params.iter().zip(&arguments) {
+            match mismatch(arg, &param.annotation) {
+                Some(t) => {
+                    interpreter_error(
+                        paren.line,
+                        parameter_type_mismatch(&function.id.lexeme, &param.id.lexeme, t, arg),
+                    );
+                    return Err(());
+                }
+                None => (),
+            }
+        }
+
+        if function.coroutine {
+            let coroutine: Coroutine = Coroutine::new(function, arguments);
+            return Ok(Content::Coroutine(Rc::new(coroutine)));
+        }
+
+        if self.depth == MAX_DEPTH {
+            interpreter_error(paren.line, STACK_OVERFLOW.to_string());
+            return Err(());
+        }
+
+        // Run inside the scope where the function was declared, not where it was called.
+        let environment: Environment = Environment::from(&function.closure);
+        let previous: Rc<RefCell<Environment>> =
+            mem::replace(&mut self.environment, Rc::new(RefCell::new(environment)));
+
+        for (param, arg) in function.params.iter().zip(arguments) {
+            self.environment
+                .borrow_mut()
+                .define_typed(&param.id, arg, &param.annotation);
+        }
+
+        let owner: bool = self.enter_class(&function.owner);
+        self.depth += 1;
+        let flow: Result<Flow, ()> = self.execute(&function.body);
+        self.depth -= 1;
+        self.leave_class(owner);
+
+        release(mem::replace(&mut self.environment, previous));
+
+        let c: Content = match flow? {
+            Flow::Return(c) => c,
+            _ => Content::Null,
+        };
+
+        match mismatch(&c, &function.output) {
+            Some(t) => {
+                interpreter_error(paren.line, return_type_mismatch(&function.id.lexeme, t, &c));
+                Err(())
+            }
+            None => Ok(c),
+        }
+    }
+
+    // Continue the coroutine until its next "yield", giving back the yielded value.
+    // Once it returns, the returned value is given back instead.
+    fn resume(&mut self, content: Content, keyword: &Token) -> Result<Content, ()> {
+        let coroutine: Rc<Coroutine> = match content {
+            Content::Coroutine(c) => c,
+            c => {
+                interpreter_error(keyword.line, not_coroutine(&c));
+                return Err(());
+            }
+        };
+
+        match coroutine.state.get() {
+            State::Suspended => (),
+            State::Running => {
+                interpreter_error(keyword.line, coroutine_running(&coroutine.id.lexeme));
+                return Err(());
+            }
+            State::Waiting => {
+                interpreter_error(keyword.line, coroutine_waiting(&coroutine.id.lexeme));
+                return Err(());
+            }
+            State::Finished => {
+                interpreter_error(keyword.line, coroutine_finished(&coroutine.id.lexeme));
+                return Err(());
+            }
+        }
+
+        self.run_coroutine(&coroutine, Content::Null)
+    }
+
+    // Run the coroutine until it stops, giving the value it was waiting for.
+    fn run_coroutine(&mut self, coroutine: &Rc<Coroutine>, value: Content) -> Result<Content, ()> {
+        let environment: Rc<RefCell<Environment>> = match coroutine.environment.borrow_mut().take()
+        {
+            Some(e) => e,
+            None => return Err(()),
+        };
+
+        let previous: Rc<RefCell<Environment>> = mem::replace(&mut self.environment, environment);
+        let mut frames: Vec<Frame> = mem::take(&mut *coroutine.frames.borrow_mut());
+
+        match coroutine.target.borrow_mut().take() {
+            Some(id) => self.environment.borrow_mut().define(&id, value),
+            None => (),
+        }
+
+        coroutine.state.set(State::Running);
+        let owner: bool = self.enter_class(&coroutine.owner);
+        let stop: Result<Stop, ()> = self.run_frames(&mut frames);
+        self.leave_class(owner);
+
+        match stop {
+            Ok(Stop::Yield(c)) => {
+                self.suspend(coroutine, frames, previous, State::Suspended);
+                Ok(c)
+            }
+            Ok(Stop::Await(target, id)) => {
+                self.suspend(coroutine, frames, previous, State::Waiting);
+                *coroutine.target.borrow_mut() = id;
+                self.wait(coroutine, target);
+                Ok(Content::Null)
+            }
+            Ok(Stop::Return(c)) => match mismatch(&c, &coroutine.output) {
+                Some(t) => {
+                    interpreter_error(
+                        coroutine.id.line,
+                        return_type_mismatch(&coroutine.id.lexeme, t, &c),
+                    );
+                    self.finish(coroutine, frames, previous, Content::Null);
+                    Err(())
+                }
+                None => {
+                    self.finish(coroutine, frames, previous, c.clone());
+                    Ok(c)
+                }
+            },
+            Err(()) => {
+                self.finish(coroutine, frames, previous, Content::Null);
+                Err(())
+            }
+        }
+    }
+
+    fn suspend(
+        &mut self,
+        coroutine: &Coroutine,
+        frames: Vec<Frame>,
+        previous: Rc<RefCell<Environment>>,
+        state: State,
+    ) {
+        let environment: Rc<RefCell<Environment>> = mem::replace(&mut self.environment, previous);
+        *coroutine.environment.borrow_mut() = Some(environment);
+        *coroutine.frames.borrow_mut() = frames;
+        coroutine.state.set(state);
+    }
+
+    // Returned or failed, so every scope it was in is left.
+    fn finish(
+        &mut self,
+        coroutine: &Coroutine,
+        mut frames: Vec<Frame>,
+        previous: Rc<RefCell<Environment>>,
+        result: Content,
+    ) {
+        while let Some(frame) = frames.pop() {
+            self.leave_frame(frame);
+        }
+
+        release(mem::replace(&mut self.environment, previous));
+        coroutine.state.set(State::Finished);
+        *coroutine.result.borrow_mut() = result.clone();
+
+        // Coroutines awaiting this one continue with its result.
+        for awaiter in coroutine.awaiters.take() {
+            self.scheduler.ready.push_back((awaiter, result.clone()));
+        }
+    }
+
+    // Leave the coroutine to the scheduler until what it awaits is done.
+    fn wait(&mut self, coroutine: &Rc<Coroutine>, target: Content) {
+        match target {
+            Content::Signal(s) => s.waiting.borrow_mut().push(Rc::clone(coroutine)),
+            Content::Coroutine(c) => match c.state.get() {
+                State::Finished => {
+                    let result: Content = c.result.borrow().clone();
+                    self.scheduler
+                        .ready
+                        .push_back((Rc::clone(coroutine), result));
+                }
+                State::Suspended => {
+                    // Nothing else is running it, so the scheduler does.
+                    c.state.set(State::Waiting);
+                    c.awaiters.borrow_mut().push(Rc::clone(coroutine));
+                    self.scheduler.ready.push_back((c, Content::Null));
+                }
+                _ => c.awaiters.borrow_mut().push(Rc::clone(coroutine)),
+            },
+            _ => (),
+        }
+    }
+
+    // Keep running coroutines until none of them can continue.
+    fn run_tasks(&mut self) -> Result<(), ()> {
+        loop {
+            while let Some((task, value)) = self.scheduler.ready.pop_front() {
+                self.run_task(&task, value)?;
+            }
+
+            match self.scheduler.next_timer() {
+                Some((time, signal)) => {
+                    self.scheduler.clock = time;
+                    self.emit_signal(&signal, Vec::new(), &signal.id)?;
+                }
+                None => return Ok(()),
+            }
         }
     }
 
-    pub fn interpret(&mut self, statements: Vec<Statement>) {
-        for s in statements {
-            self.execute(s);
+    fn run_task(&mut self, task: &Rc<Coroutine>, value: Content) -> Result<(), ()> {
+        self.run_coroutine(task, value)?;
+
+        // A "yield" lets the other tasks run before it continues.
+        if task.state.get() == State::Suspended {
+            task.state.set(State::Waiting);
+            self.scheduler
+                .ready
+                .push_back((Rc::clone(task), Content::Null));
         }
+
+        Ok(())
     }
 
-    // Analogue to evaluate() but for statements.
-    fn execute(&mut self, stmt: Statement) {
-        let _ = match stmt {
-            Statement::Var { identifier } => self.var(identifier),
-            Statement::VarAssign { identifier, expr } => self.var_assign(identifier, *expr),
+    // Statements are run one at a time, keeping track of where it is in the frames
+    // instead of the Rust stack, so it can stop in the middle of any block.
+    fn run_frames(&mut self, frames: &mut Vec<Frame>) -> Result<Stop, ()> {
+        loop {
+            let stop: Option<Stop> = self.step(frames)?;
+            self.destroy()?;
+
+            match stop {
+                Some(s) => return Ok(s),
+                None => (),
+            }
+        }
+    }
+
+    fn step(&mut self, frames: &mut Vec<Frame>) -> Result<Option<Stop>, ()> {
+        let frame: &mut Frame = match frames.last_mut() {
+            Some(f) => f,
+            None => return Ok(Some(Stop::Return(Content::Null))),
+        };
+
+        match frame {
+            Frame::Block { block, index } => {
+                let stmt: Option<Rc<Statement>> = match &**block {
+                    Statement::Block { stmts, level: _ } => stmts.get(*index).cloned(),
+                    _ => None,
+                };
+                *index += 1;
+
+                match stmt {
+                    Some(s) => return self.step_statement(frames, &s),
+                    None => self.pop_frame(frames),
+                }
+            }
+            Frame::While { stmt } => {
+                let stmt: Rc<Statement> = Rc::clone(stmt);
+
+                match &*stmt {
+                    Statement::While {
+                        condition,
+                        statement,
+                    } => {
+                        if is_true(&self.evaluate(condition)?) {
+                            self.push_block(frames, statement);
+                        } else {
+                            self.pop_frame(frames);
+                        }
+                    }
+                    _ => self.pop_frame(frames),
+                }
+            }
+            Frame::Loop { statement } => {
+                let statement: Rc<Statement> = Rc::clone(statement);
+                self.push_block(frames, &statement);
+            }
+            Frame::For {
+                id,
+                iteration,
+                statement,
+            } => match self.next(iteration, id)? {
+                Some(c) => {
+                    let statement: Rc<Statement> = Rc::clone(statement);
+
+                    // Each iteration gets a fresh loop variable.
+                    self.begin_scope();
+                    self.environment.borrow_mut().define(id, c);
+                    frames.push(Frame::Scope);
+                    self.push_block(frames, &statement);
+                }
+                None => self.pop_frame(frames),
+            },
+            Frame::Scope => self.pop_frame(frames),
+        }
+
+        Ok(None)
+    }
+
+    // Statements that contain other statements become frames, the rest run at once.
+    fn step_statement(
+        &mut self,
+        frames: &mut Vec<Frame>,
+        stmt: &Rc<Statement>,
+    ) -> Result<Option<Stop>, ()> {
+        match &**stmt {
+            Statement::Block { stmts: _, level: _ } => self.push_block(frames, stmt),
             Statement::If {
                 condition,
                 statement,
-            } => self.if_(*condition, *statement),
+            } => {
+                if is_true(&self.evaluate(condition)?) {
+                    self.push_block(frames, statement);
+                }
+            }
             Statement::IfElse {
                 condition,
                 if_statement,
                 else_statement,
-            } => self.if_else(*condition, *if_statement, *else_statement),
-            Statement::Print { expr } => self.print(*expr),
-            Statement::Block { stmts, level: _ } => self.block(stmts),
-            Statement::Expr { expr } => self.expression(*expr),
-        };
+            } => {
+                if is_true(&self.evaluate(condition)?) {
+                    self.push_block(frames, if_statement);
+                } else {
+                    self.push_block(frames, else_statement);
+                }
+            }
+            Statement::While {
+                condition: _,
+                statement: _,
+            } => frames.push(Frame::While {
+                stmt: Rc::clone(stmt),
+            }),
+            Statement::Loop { statement } => frames.push(Frame::Loop {
+                statement: Rc::clone(statement),
+            }),
+            Statement::For {
+                id,
+                iterable,
+                statement,
+            } => {
+                let c: Content = self.evaluate(iterable)?;
+                let iteration: Iteration = self.iteration(c, id)?;
+
+                frames.push(Frame::For {
+                    id: id.clone(),
+                    iteration: iteration,
+                    statement: Rc::clone(statement),
+                });
+            }
+            Statement::Match { token, expr, arms } => self.step_match(frames, token, expr, arms)?,
+            Statement::Break => self.unwind(frames, true),
+            Statement::Continue => self.unwind(frames, false),
+            Statement::Return { expr } => match expr {
+                Some(e) => return Ok(Some(Stop::Return(self.evaluate(e)?))),
+                None => return Ok(Some(Stop::Return(Content::Null))),
+            },
+            Statement::Yield { keyword: _, expr } => match expr {
+                Some(e) => return Ok(Some(Stop::Yield(self.evaluate(e)?))),
+                None => return Ok(Some(Stop::Yield(Content::Null))),
+            },
+            Statement::Await { keyword, expr, id } => {
+                let target: Content = self.evaluate(expr)?;
+
+                match &target {
+                    Content::Signal(_) => (),
+                    Content::Coroutine(c) if c.state.get() == State::Running => {
+                        interpreter_error(keyword.line, coroutine_running(&c.id.lexeme));
+                        return Err(());
+                    }
+                    Content::Coroutine(_) => (),
+                    c => {
+                        interpreter_error(keyword.line, await_invalid(c));
+                        return Err(());
+                    }
+                }
+
+                return Ok(Some(Stop::Await(target, id.clone())));
+            }
+            _ => {
+                self.execute(stmt)?;
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn step_match(
+        &mut self,
+        frames: &mut Vec<Frame>,
+        token: &Token,
+        expr: &Expression,
+        arms: &Vec<Arm>,
+    ) -> Result<(), ()> {
+        let c: Content = self.evaluate(expr)?;
+
+        for arm in arms {
+            // Bindings from the pattern only live inside the arm.
+            self.begin_scope();
+
+            match self.is_arm(&c, arm) {
+                Ok(true) => {
+                    frames.push(Frame::Scope);
+                    self.push_block(frames, &arm.statement);
+                    return Ok(());
+                }
+                Ok(false) => self.end_scope(),
+                Err(()) => {
+                    self.end_scope();
+                    return Err(());
+                }
+            }
+        }
+
+        interpreter_error(token.line, no_match(&c));
+        Err(())
+    }
+
+    fn push_block(&mut self, frames: &mut Vec<Frame>, block: &Rc<Statement>) {
+        self.begin_scope();
+        frames.push(Frame::Block {
+            block: Rc::clone(block),
+            index: 0,
+        });
+    }
+
+    fn pop_frame(&mut self, frames: &mut Vec<Frame>) {
+        match frames.pop() {
+            Some(f) => self.leave_frame(f),
+            None => (),
+        }
+    }
+
+    fn leave_frame(&mut self, frame: Frame) {
+        match frame {
+            Frame::Block { block: _, index: _ } | Frame::Scope => self.end_scope(),
+            _ => (),
+        }
+    }
+
+    // Leave the frames inside the innermost loop, and also the loop on "break".
+    fn unwind(&mut self, frames: &mut Vec<Frame>, leave: bool) {
+        while let Some(frame) = frames.last() {
+            if frame.is_loop() {
+                if leave {
+                    self.pop_frame(frames);
+                }
+
+                return;
+            }
+
+            self.pop_frame(frames);
+        }
     }
 
-    fn var(&mut self, id: Token) {
-        self.environment.define(&id, Content::Null);
+    // Run a "get" or "set" block, where the field name refers to its stored value.
+    // Returns the result of the block and the stored value after it.
+    fn call_accessor(
+        &mut self,
+        accessor: &Function,
+        object: &Content,
+        field: &Token,
+        stored: Content,
+        arguments: Vec<Content>,
+    ) -> Result<(Content, Content), ()> {
+        let accessor: Function = accessor.bind(object.clone());
+        let environment: Environment = Environment::from(&accessor.closure);
+        let previous: Rc<RefCell<Environment>> =
+            mem::replace(&mut self.environment, Rc::new(RefCell::new(environment)));
+
+        self.environment.borrow_mut().define(field, stored);
+
+        for (param, arg) in accessor.params.iter().zip(arguments) {
+            self.environment.borrow_mut().define(&param.id, arg);
+        }
+
+        let accessing: bool = match object {
+            Content::Instance(i) => {
+                let environment: Rc<RefCell<Environment>> = Rc::clone(&self.environment);
+                let address: usize = Rc::as_ptr(i) as *const () as usize;
+                self.accessors
+                    .push((address, field.lexeme.clone(), environment));
+                true
+            }
+            _ => false,
+        };
+
+        let owner: bool = self.enter_class(&accessor.owner);
+        let flow: Result<Flow, ()> = self.execute(&accessor.body);
+        self.leave_class(owner);
+        let stored: Result<Content, ()> = self.environment.borrow().get(field);
+
+        if accessing {
+            self.accessors.pop();
+        }
+
+        release(mem::replace(&mut self.environment, previous));
+
+        match flow? {
+            Flow::Return(c) => Ok((c, stored?)),
+            _ => Ok((Content::Null, stored?)),
+        }
     }
 
-    fn var_assign(&mut self, id: Token, expr: Expression) {
-        let expr = match self.evaluate(expr) {
-            Ok(c) => c,
-            _ => return,
+    // Inside its own accessors, the field is the stored value instead of calling them again.
+    fn accessor_scope(&self, object: &Content, name: &Token) -> Option<Rc<RefCell<Environment>>> {
+        let address: usize = match object {
+            Content::Instance(i) => Rc::as_ptr(i) as *const () as usize,
+            _ => return None,
         };
 
-        self.environment.define(&id, expr);
+        self.accessors
+            .iter()
+            .rev()
+            .find(|(a, f, _)| *a == address && *f == name.lexeme)
+            .map(|(_, _, e)| Rc::clone(e))
     }
 
-    fn if_(&mut self, condition: Expression, statement: Statement) {
-        match self.evaluate(condition) {
-            Ok(c) => {
-                if is_true(&c) {
-                    self.execute(statement)
+    // Methods can reach the private members of their class.
+    fn enter_class(&mut self, owner: &Option<Weak<Class>>) -> bool {
+        match owner.as_ref().and_then(|o| o.upgrade()) {
+            Some(c) => {
+                self.contexts.push(c);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn leave_class(&mut self, entered: bool) {
+        if entered {
+            self.contexts.pop();
+        }
+    }
+
+    // Members that aren't public are only visible to the class and its subclasses.
+    fn check_access(&self, class: &Class, name: &Token) -> Result<(), ()> {
+        let context: Option<&Class> = self.contexts.last().map(|c| &**c);
+
+        match (
+            class.is_visible(&name.lexeme, context),
+            class.owner(&name.lexeme),
+        ) {
+            (false, Some(owner)) => {
+                interpreter_error(name.line, member_private(&owner.id.lexeme, &name.lexeme));
+                Err(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn get(&mut self, object: &Expression, name: &Token) -> Result<Content, ()> {
+        let object: Content = self.evaluate(object)?;
+        self.property(&object, name)
+    }
+
+    fn safe_get(&mut self, object: &Expression, name: &Token) -> Result<Content, ()> {
+        match self.evaluate(object)? {
+            Content::Null => Ok(Content::Null),
+            object => self.property(&object, name),
+        }
+    }
+
+    // Analogue to property() but missing members are reported as methods.
+    fn method(&mut self, object: &Content, name: &Token) -> Result<Content, ()> {
+        match object {
+            Content::Instance(i) => {
+                let instance: Ref<Instance> = i.borrow();
+
+                if instance.get(&name.lexeme).is_none()
+                    && !instance.signals.contains_key(&name.lexeme)
+                    && instance.class.find_method(&name.lexeme).is_none()
+                {
+                    interpreter_error(
+                        name.line,
+                        method_undefined(&instance.class.id.lexeme, &name.lexeme),
+                    );
+                    return Err(());
+                }
+            }
+            Content::Class(c) if c.kind == Kind::Class => {
+                if c.find_static(&name.lexeme).is_none()
+                    && c.find_static_method(&name.lexeme).is_none()
+                {
+                    interpreter_error(name.line, method_undefined(&c.id.lexeme, &name.lexeme));
+                    return Err(());
                 }
             }
             _ => (),
         }
+
+        self.property(object, name)
     }
 
-    fn if_else(
-        &mut self,
-        condition: Expression,
-        if_statement: Statement,
-        else_statement: Statement,
-    ) {
-        match self.evaluate(condition) {
-            Ok(c) => {
-                if is_true(&c) {
-                    self.execute(if_statement)
+    fn property(&mut self, object: &Content, name: &Token) -> Result<Content, ()> {
+        // Fields shadow methods with the same name.
+        match self.accessor_scope(object, name) {
+            Some(e) => return e.borrow().get(name),
+            None => (),
+        }
+
+        let c: Option<Content> = match object {
+            Content::Instance(i) => {
+                let instance: Ref<Instance> = i.borrow();
+                self.check_access(&instance.class, name)?;
+
+                let field: Option<Content> = instance.get(&name.lexeme);
+                let getter: Option<Rc<Function>> = instance.class.find_getter(&name.lexeme);
+                let signal: Option<Rc<Signal>> = instance.signals.get(&name.lexeme).cloned();
+                let method: Option<Rc<Function>> = instance.class.find_method(&name.lexeme);
+                drop(instance);
+
+                match (field, getter) {
+                    (Some(f), Some(g)) => {
+                        Some(self.call_accessor(&g, object, name, f, Vec::new())?.0)
+                    }
+                    (Some(f), None) => Some(f),
+                    (None, _) => match signal {
+                        Some(s) => Some(Content::Signal(s)),
+                        None => method.map(|m| Content::Function(Rc::new(m.bind(object.clone())))),
+                    },
+                }
+            }
+            Content::Signal(s) => {
+                Signal::method(s, &name.lexeme).map(|n| Content::Native(Rc::new(n)))
+            }
+            Content::Module(m) => match m.get(&name.lexeme) {
+                Some(Content::Singleton(s)) => Some(self.singleton(&s, name)?),
+                Some(c) => Some(c),
+                None => {
+                    interpreter_error(name.line, module_member_undefined(&m.id, &name.lexeme));
+                    return Err(());
+                }
+            },
+            Content::Coroutine(c) if name.lexeme == "state" => {
+                Some(Content::String_(c.state_to_string()))
+            }
+            Content::Class(c) => {
+                self.check_access(c, name)?;
+
+                match c.find_static(&name.lexeme) {
+                    Some(s) => Some(s),
+                    None => c.find_static_method(&name.lexeme).map(Content::Function),
+                }
+            }
+            _ => None,
+        };
+
+        match c {
+            Some(c) => return Ok(c),
+            None => (),
+        }
+
+        match object {
+            Content::Enum(e) if e.get_variant(&name.lexeme).is_some() => {
+                let variant: Variant = Variant {
+                    enumeration: Rc::clone(e),
+                    name: name.lexeme.clone(),
+                    values: Vec::new(),
+                };
+
+                if variant.arity() == 0 {
+                    Ok(Content::Variant(variant))
                 } else {
-                    self.execute(else_statement)
+                    Ok(Content::Constructor(variant))
                 }
             }
-            _ => (),
+            _ => {
+                interpreter_error(name.line, property_undefined(&object, &name.lexeme));
+                Err(())
+            }
         }
     }
 
-    fn print(&mut self, expr: Expression) {
-        match self.evaluate(expr) {
-            Ok(c) => println!("{}", c.to_string()),
-            _ => (),
+    fn call_struct(
+        &mut self,
+        structure: &Rc<Class>,
+        arguments: Vec<Content>,
+        named: Vec<(Token, Content)>,
+        paren: &Token,
+    ) -> Result<Content, ()> {
+        let names: Vec<String> = structure.field_names();
+
+        if arguments.len() > names.len() {
+            interpreter_error(
+                paren.line,
+                arity_mismatch(&structure.id.lexeme, names.len(), arguments.len()),
+            );
+            return Err(());
+        }
+
+        let mut values: HashMap<String, Content> = HashMap::new();
+
+        for (name, arg) in names.into_iter().zip(arguments) {
+            match mismatch(&arg, &structure.find_type(&name)) {
+                Some(t) => {
+                    interpreter_error(paren.line, field_type_mismatch(&name, &t, &arg));
+                    return Err(());
+                }
+                None => (),
+            }
+
+            values.insert(name, arg);
+        }
+
+        for (name, arg) in named {
+            if !structure.has_field(&name.lexeme) {
+                let c: Content = Content::Class(Rc::clone(structure));
+                interpreter_error(name.line, property_undefined(&c, &name.lexeme));
+                return Err(());
+            }
+
+            if values.contains_key(&name.lexeme) {
+                interpreter_error(name.line, argument_duplicated(&name.lexeme));
+                return Err(());
+            }
+
+            match mismatch(&arg, &structure.find_type(&name.lexeme)) {
+                Some(t) => {
+                    interpreter_error(name.line, field_type_mismatch(&name.lexeme, &t, &arg));
+                    return Err(());
+                }
+                None => (),
+            }
+
+            values.insert(name.lexeme.clone(), arg);
         }
+
+        self.initialize(structure, &mut values)?;
+
+        let instance: Instance = Instance {
+            class: Rc::clone(structure),
+            values: values,
+            signals: HashMap::new(),
+            destroyed: false,
+        };
+
+        Ok(Content::Instance(Rc::new(RefCell::new(instance))))
     }
 
-    fn block(&mut self, stmts: Vec<Statement>) {
-        self.environment = Environment::from(self.environment.clone());
+    fn call_class(
+        &mut self,
+        class: &Rc<Class>,
+        arguments: Vec<Content>,
+        paren: &Token,
+    ) -> Result<Content, ()> {
+        let constructor: Option<Rc<Function>> = class.find_method("constructor");
+
+        // Without constructor there is nothing to receive arguments.
+        if constructor.is_none() && arguments.len() != 0 {
+            interpreter_error(
+                paren.line,
+                arity_mismatch(&class.id.lexeme, 0, arguments.len()),
+            );
+            return Err(());
+        }
+
+        let mut values: HashMap<String, Content> = HashMap::new();
+        self.initialize(class, &mut values)?;
 
-        for stmt in stmts {
-            self.execute(stmt)
+        let instance: Instance = Instance {
+            class: Rc::clone(class),
+            values: values,
+            signals: class.create_signals(),
+            destroyed: false,
+        };
+        let instance: Content = Content::Instance(Rc::new(RefCell::new(instance)));
+
+        match constructor {
+            Some(c) => {
+                self.call_function(&c.bind(instance.clone()), arguments, paren)?;
+            }
+            None => (),
         }
 
-        self.environment = match &self.environment.enclosing {
-            Some(e) => *e.clone(),
-            None => self.environment.clone(),
+        Ok(instance)
+    }
+
+    // Call destructors in the order their instances were released.
+    fn destroy(&mut self) -> Result<(), ()> {
+        let mut result: Result<(), ()> = Ok(());
+
+        loop {
+            let destroyed: Vec<Instance> = DESTROYED.with(|d| mem::take(&mut *d.borrow_mut()));
+            let collected: Vec<Rc<RefCell<Instance>>> =
+                COLLECTED.with(|c| mem::take(&mut *c.borrow_mut()));
+
+            if destroyed.is_empty() && collected.is_empty() {
+                return result;
+            }
+
+            for instance in destroyed {
+                let destructor: Rc<Function> = match instance.class.find_method("destructor") {
+                    Some(d) => d,
+                    None => continue,
+                };
+
+                let instance: Content = Content::Instance(Rc::new(RefCell::new(instance)));
+                let destructor: Function = destructor.bind(instance);
+
+                // Every destructor runs, even if a previous one failed.
+                if self
+                    .call_function(&destructor, Vec::new(), &destructor.id)
+                    .is_err()
+                {
+                    result = Err(());
+                }
+            }
+
+            // Instances of a cycle can still see each other in their destructors.
+            for instance in &collected {
+                let destructor: Rc<Function> =
+                    match instance.borrow().class.find_method("destructor") {
+                        Some(d) => d,
+                        None => continue,
+                    };
+
+                let destructor: Function = destructor.bind(Content::Instance(Rc::clone(instance)));
+
+                if self
+                    .call_function(&destructor, Vec::new(), &destructor.id)
+                    .is_err()
+                {
+                    result = Err(());
+                }
+            }
+
+            // Releasing the fields breaks the cycles.
+            for instance in collected {
+                let values: Vec<Content> = instance.borrow_mut().release();
+
+                for value in values {
+                    dispose(value);
+                }
+            }
         }
     }
 
-    fn expression(&mut self, expr: Expression) {
-        match self.evaluate(expr) {
-            _ => (),
+    // Missing fields use their defaults, evaluated where their class was declared.
+    fn initialize(
+        &mut self,
+        class: &Class,
+        values: &mut HashMap<String, Content>,
+    ) -> Result<(), ()> {
+        match &class.superclass {
+            Some(s) => self.initialize(s, values)?,
+            None => (),
         }
+
+        let previous: Rc<RefCell<Environment>> =
+            mem::replace(&mut self.environment, Rc::clone(&class.closure));
+        let mut result: Result<(), ()> = Ok(());
+
+        for field in &class.fields {
+            if values.contains_key(&field.id.lexeme) {
+                continue;
+            }
+
+            let c: Content = match &field.default {
+                Some(d) => match self.evaluate(d) {
+                    Ok(c) => c,
+                    Err(_) => {
+                        result = Err(());
+                        break;
+                    }
+                },
+                None => Content::Null,
+            };
+
+            match (&field.default, mismatch(&c, &field.annotation)) {
+                (Some(_), Some(t)) => {
+                    interpreter_error(field.id.line, field_type_mismatch(&field.id.lexeme, t, &c));
+                    result = Err(());
+                    break;
+                }
+                _ => (),
+            }
+
+            values.insert(field.id.lexeme.clone(), c);
+        }
+
+        self.environment = previous;
+        result
     }
 
-    // Analogue to execute() but for expressions.
-    fn evaluate(&mut self, expr: Expression) -> Result<Content, ()> {
-        let c: Content = match expr {
-            Expression::Literal { token } => Content::from(token.token_type)?,
-            Expression::Variable { id } => self.environment.get(&id)?,
-            Expression::Grouping { expr } => self.evaluate(*expr)?,
-            Expression::Unary { op, right } => self.unary(op, *right)?,
-            Expression::Binary { left, op, right } => self.binary(*left, op, *right)?,
-            Expression::Logical { left, op, right } => self.logical(*left, op, *right)?,
-            Expression::Assignment { id, op, right } => self.assignment(id, op, *right)?,
+    fn super_(&mut self, keyword: &Token, method: &Token) -> Result<Content, ()> {
+        let self_: Token = Token {
+            token_type: TokenType::Self_,
+            lexeme: "self".to_string(),
+            line: keyword.line,
         };
+        let superclass: Content = self.environment.borrow().get(keyword)?;
+        let instance: Content = self.environment.borrow().get(&self_)?;
 
-        Ok(c)
+        let superclass: Rc<Class> = match superclass {
+            Content::Class(c) => c,
+            c => {
+                interpreter_error(keyword.line, superclass_invalid(&c));
+                return Err(());
+            }
+        };
+
+        match superclass.find_method(&method.lexeme) {
+            Some(m) => Ok(Content::Function(Rc::new(m.bind(instance)))),
+            None => {
+                interpreter_error(
+                    method.line,
+                    method_undefined(&superclass.id.lexeme, &method.lexeme),
+                );
+                Err(())
+            }
+        }
+    }
+
+    fn call_constructor(
+        &mut self,
+        mut variant: Variant,
+        arguments: Vec<Content>,
+        paren: &Token,
+    ) -> Result<Content, ()> {
+        if arguments.len() != variant.arity() {
+            interpreter_error(
+                paren.line,
+                arity_mismatch(&variant.to_string(), variant.arity(), arguments.len()),
+            );
+            return Err(());
+        }
+
+        variant.values = arguments;
+        Ok(Content::Variant(variant))
     }
 
-    fn unary(&mut self, op: Token, right: Expression) -> Result<Content, ()> {
+    fn unary(&mut self, op: &Token, right: &Expression) -> Result<Content, ()> {
         let content: Content = self.evaluate(right)?;
 
         let c: Content = match op.token_type {
@@ -143,13 +2299,15 @@ impl Interpreter {
             TokenType::Not => self.unary_not(content, op)?,
             // Math
             TokenType::Minus => self.unary_minus(content, op)?,
+            // Coroutine
+            TokenType::Resume => self.resume(content, op)?,
             _ => return Err(()),
         };
 
         Ok(c)
     }
 
-    fn unary_minus(&self, content: Content, op: Token) -> Result<Content, ()> {
+    fn unary_minus(&self, content: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match content {
             Content::Integer(i) => Content::Integer(-i),
             Content::Floating(f) => Content::Floating(-f),
@@ -162,11 +2320,11 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn unary_not(&self, content: Content, _op: Token) -> Result<Content, ()> {
+    fn unary_not(&self, content: Content, _op: &Token) -> Result<Content, ()> {
         Ok(Content::Boolean(!is_true(&content)))
     }
 
-    fn unary_exclamation_mark(&self, content: Content, op: Token) -> Result<Content, ()> {
+    fn unary_exclamation_mark(&self, content: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match content {
             Content::Integer(i) => Content::Integer(!i),
             _ => {
@@ -178,7 +2336,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary(&mut self, left: Expression, op: Token, right: Expression) -> Result<Content, ()> {
+    fn binary(&mut self, left: &Expression, op: &Token, right: &Expression) -> Result<Content, ()> {
         let l_content: Content = self.evaluate(left)?;
         let r_content: Content = self.evaluate(right)?;
 
@@ -196,6 +2354,7 @@ impl Interpreter {
             TokenType::NotEqual => self.binary_not_equal(l_content, r_content, op)?,
             TokenType::GreaterEqual => self.binary_greater_equal(l_content, r_content, op)?,
             TokenType::LessEqual => self.binary_less_equal(l_content, r_content, op)?,
+            TokenType::Is => self.binary_is(l_content, r_content, op)?,
             // Math
             TokenType::Plus => self.binary_plus(l_content, r_content, op)?,
             TokenType::Minus => self.binary_minus(l_content, r_content, op)?,
@@ -209,7 +2368,7 @@ impl Interpreter {
         Ok(c2)
     }
 
-    fn binary_ampersand(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_ampersand(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Integer(*i1 & *i2),
             _ => {
@@ -221,7 +2380,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_pipe(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_pipe(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Integer(*i1 | *i2),
             _ => {
@@ -233,7 +2392,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_caret(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_caret(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Integer(*i1 ^ *i2),
             _ => {
@@ -249,7 +2408,7 @@ impl Interpreter {
         &self,
         left: Content,
         right: Content,
-        op: Token,
+        op: &Token,
     ) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Integer(*i1 >> *i2),
@@ -262,7 +2421,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_less_less(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_less_less(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Integer(*i1 << *i2),
             _ => {
@@ -274,7 +2433,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_greater(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_greater(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Boolean(*i1 > *i2),
             (Content::Integer(i1), Content::Floating(f2)) => Content::Boolean((*i1 as f32) > *f2),
@@ -289,7 +2448,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_less(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_less(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Boolean(*i1 < *i2),
             (Content::Integer(i1), Content::Floating(f2)) => Content::Boolean((*i1 as f32) < *f2),
@@ -304,7 +2463,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_equal(&self, left: Content, right: Content, _token: Token) -> Result<Content, ()> {
+    fn binary_equal(&self, left: Content, right: Content, _token: &Token) -> Result<Content, ()> {
         Ok(Content::Boolean(left == right))
     }
 
@@ -312,7 +2471,7 @@ impl Interpreter {
         &self,
         left: Content,
         right: Content,
-        _token: Token,
+        _token: &Token,
     ) -> Result<Content, ()> {
         Ok(Content::Boolean(left != right))
     }
@@ -321,7 +2480,7 @@ impl Interpreter {
         &self,
         left: Content,
         right: Content,
-        op: Token,
+        op: &Token,
     ) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Boolean(*i1 >= *i2),
@@ -337,7 +2496,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_less_equal(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_less_equal(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Boolean(*i1 <= *i2),
             (Content::Integer(i1), Content::Floating(f2)) => Content::Boolean((*i1 as f32) <= *f2),
@@ -352,7 +2511,22 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_plus(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_is(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
+        let b: bool = match (&left, &right) {
+            (Content::Instance(i), Content::Class(c)) => i.borrow().class.is_subclass_of(c),
+            (Content::Instance(i), Content::Interface(t)) => i.borrow().class.implements(t),
+            (Content::Variant(v), Content::Enum(e)) => v.enumeration == *e,
+            (_, Content::Class(_)) | (_, Content::Interface(_)) | (_, Content::Enum(_)) => false,
+            _ => {
+                interpreter_error(op.line, type_unsupported(&right));
+                return Err(());
+            }
+        };
+
+        Ok(Content::Boolean(b))
+    }
+
+    fn binary_plus(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Integer(*i1 + *i2),
             (Content::Integer(i1), Content::Floating(f2)) => Content::Floating((*i1 as f32) + *f2),
@@ -370,7 +2544,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_minus(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_minus(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Integer(*i1 - *i2),
             (Content::Integer(i1), Content::Floating(f2)) => Content::Floating((*i1 as f32) - *f2),
@@ -385,7 +2559,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_star(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_star(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Integer(*i1 * *i2),
             (Content::Integer(i1), Content::Floating(f2)) => Content::Floating((*i1 as f32) * *f2),
@@ -400,7 +2574,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_slash(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_slash(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Integer(*i1 / *i2),
             (Content::Integer(i1), Content::Floating(f2)) => Content::Floating((*i1 as f32) / *f2),
@@ -415,7 +2589,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_percentage(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_percentage(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => Content::Integer(*i1 % *i2),
             (Content::Integer(i1), Content::Floating(f2)) => Content::Floating((*i1 as f32) % *f2),
@@ -430,7 +2604,7 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn binary_starstar(&self, left: Content, right: Content, op: Token) -> Result<Content, ()> {
+    fn binary_starstar(&self, left: Content, right: Content, op: &Token) -> Result<Content, ()> {
         let c: Content = match (&left, &right) {
             (Content::Integer(i1), Content::Integer(i2)) => {
                 let i3: i32 = (*i1).pow(*i2 as u32);
@@ -457,7 +2631,51 @@ impl Interpreter {
         Ok(c)
     }
 
-    fn logical(&mut self, left: Expression, op: Token, right: Expression) -> Result<Content, ()> {
+    fn range(
+        &mut self,
+        start: &Expression,
+        op: &Token,
+        end: &Expression,
+        step: &Option<Box<Expression>>,
+    ) -> Result<Content, ()> {
+        let start: Content = self.evaluate(start)?;
+        let end: Content = self.evaluate(end)?;
+
+        let step: i32 = match step {
+            Some(s) => match self.evaluate(s)? {
+                Content::Integer(i) if i != 0 => i,
+                c => {
+                    interpreter_error(op.line, range_invalid_step(&c));
+                    return Err(());
+                }
+            },
+            None => 1,
+        };
+
+        let (start, end, character) = match (&start, &end) {
+            (Content::Integer(i1), Content::Integer(i2)) => (*i1, *i2, false),
+            (Content::Character(c1), Content::Character(c2)) => (*c1 as i32, *c2 as i32, true),
+            _ => {
+                interpreter_error(op.line, range_unsupported(&start, &end));
+                return Err(());
+            }
+        };
+
+        Ok(Content::Range(Range {
+            start: start,
+            end: end,
+            step: step,
+            inclusive: op.token_type == TokenType::PeriodPeriodEqual,
+            character: character,
+        }))
+    }
+
+    fn logical(
+        &mut self,
+        left: &Expression,
+        op: &Token,
+        right: &Expression,
+    ) -> Result<Content, ()> {
         let mut c: Content = self.evaluate(left)?;
 
         c = match op.token_type {
@@ -475,72 +2693,127 @@ impl Interpreter {
                     self.evaluate(right)?
                 }
             }
+            TokenType::QuestionMarkQuestionMark => match c {
+                Content::Null => self.evaluate(right)?,
+                c => c,
+            },
             _ => return Err(()),
         };
 
         Ok(c)
     }
 
-    fn assignment(&mut self, id: Token, op: Token, right: Expression) -> Result<Content, ()> {
+    fn assignment(&mut self, id: &Token, op: &Token, right: &Expression) -> Result<Content, ()> {
         let mut c: Content = self.evaluate(right)?;
 
-        c = match op.token_type {
-            TokenType::Equal => self.environment.assign(&id, c)?,
-            TokenType::PlusEqual => {
-                let left = self.environment.get(&id)?;
-                c = self.binary_plus(left, c, op)?;
-                self.environment.assign(&id, c)?
-            }
-            TokenType::MinusEqual => {
-                let left = self.environment.get(&id)?;
-                c = self.binary_minus(left, c, op)?;
-                self.environment.assign(&id, c)?
-            }
-            TokenType::StarEqual => {
-                let left = self.environment.get(&id)?;
-                c = self.binary_star(left, c, op)?;
-                self.environment.assign(&id, c)?
-            }
-            TokenType::SlashEqual => {
-                let left = self.environment.get(&id)?;
-                c = self.binary_slash(left, c, op)?;
-                self.environment.assign(&id, c)?
-            }
-            TokenType::PercentageEqual => {
-                let left = self.environment.get(&id)?;
-                c = self.binary_percentage(left, c, op)?;
-                self.environment.assign(&id, c)?
-            }
-            TokenType::StarStarEqual => {
-                let left = self.environment.get(&id)?;
-                c = self.binary_starstar(left, c, op)?;
-                self.environment.assign(&id, c)?
-            }
-            TokenType::AmpersandEqual => {
-                let left = self.environment.get(&id)?;
-                c = self.binary_ampersand(left, c, op)?;
-                self.environment.assign(&id, c)?
-            }
-            TokenType::PipeEqual => {
-                let left = self.environment.get(&id)?;
-                c = self.binary_pipe(left, c, op)?;
-                self.environment.assign(&id, c)?
-            }
-            TokenType::CaretEqual => {
-                let left = self.environment.get(&id)?;
-                c = self.binary_caret(left, c, op)?;
-                self.environment.assign(&id, c)?
-            }
-            TokenType::GreaterGreaterEqual => {
-                let left = self.environment.get(&id)?;
-                c = self.binary_greater_greater(left, c, op)?;
-                self.environment.assign(&id, c)?
-            }
-            TokenType::LessLessEqual => {
-                let left = self.environment.get(&id)?;
-                c = self.binary_less_less(left, c, op)?;
-                self.environment.assign(&id, c)?
+        if op.token_type != TokenType::Equal {
+            let left: Content = self.environment.borrow().get(id)?;
+            c = self.compound_assignment(left, c, op)?;
+        }
+
+        let previous: Content = self.environment.borrow_mut().assign(id, c)?;
+        dispose(previous);
+
+        Ok(Content::Null)
+    }
+
+    fn set(
+        &mut self,
+        object: &Expression,
+        name: &Token,
+        op: &Token,
+        right: &Expression,
+    ) -> Result<Content, ()> {
+        let object: Content = self.evaluate(object)?;
+        let mut c: Content = self.evaluate(right)?;
+
+        match &object {
+            Content::Instance(i) => self.check_access(&i.borrow().class, name)?,
+            Content::Class(c) => self.check_access(c, name)?,
+            _ => {
+                interpreter_error(name.line, property_unsettable(&object));
+                return Err(());
+            }
+        }
+
+        if op.token_type != TokenType::Equal {
+            let left: Content = self.property(&object, name)?;
+            c = self.compound_assignment(left, c, op)?;
+        }
+
+        let previous: Option<Content> = match &object {
+            Content::Instance(i) => match self.accessor_scope(&object, name) {
+                Some(e) => {
+                    let type_: Option<Type> = i.borrow().class.find_type(&name.lexeme);
+                    self.check_field(name, &c, &type_)?;
+                    let previous: Content = e.borrow_mut().assign(name, c)?;
+                    Some(previous)
+                }
+                None => {
+                    let field: Option<Content> = i.borrow().get(&name.lexeme);
+                    let setter: Option<Rc<Function>> = i.borrow().class.find_setter(&name.lexeme);
+
+                    // The setter decides what is stored in the field.
+                    match (field, setter) {
+                        (Some(f), Some(s)) => {
+                            c = self.call_accessor(&s, &object, name, f, vec![c])?.1
+                        }
+                        _ => (),
+                    }
+
+                    let type_: Option<Type> = i.borrow().class.find_type(&name.lexeme);
+                    self.check_field(name, &c, &type_)?;
+                    i.borrow_mut().set(&name.lexeme, c)
+                }
+            },
+            Content::Class(class) => {
+                self.check_field(name, &c, &class.find_type(&name.lexeme))?;
+                class.set_static(&name.lexeme, c)
             }
+            _ => None,
+        };
+
+        match previous {
+            Some(p) => {
+                dispose(p);
+                Ok(Content::Null)
+            }
+            None => {
+                interpreter_error(name.line, property_undefined(&object, &name.lexeme));
+                Err(())
+            }
+        }
+    }
+
+    fn check_field(&self, name: &Token, c: &Content, annotation: &Option<Type>) -> Result<(), ()> {
+        match mismatch(c, annotation) {
+            Some(t) => {
+                interpreter_error(name.line, field_type_mismatch(&name.lexeme, t, c));
+                Err(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    // Apply the operation behind assignments like "+=".
+    fn compound_assignment(
+        &self,
+        left: Content,
+        right: Content,
+        op: &Token,
+    ) -> Result<Content, ()> {
+        let c: Content = match op.token_type {
+            TokenType::PlusEqual => self.binary_plus(left, right, op)?,
+            TokenType::MinusEqual => self.binary_minus(left, right, op)?,
+            TokenType::StarEqual => self.binary_star(left, right, op)?,
+            TokenType::SlashEqual => self.binary_slash(left, right, op)?,
+            TokenType::PercentageEqual => self.binary_percentage(left, right, op)?,
+            TokenType::StarStarEqual => self.binary_starstar(left, right, op)?,
+            TokenType::AmpersandEqual => self.binary_ampersand(left, right, op)?,
+            TokenType::PipeEqual => self.binary_pipe(left, right, op)?,
+            TokenType::CaretEqual => self.binary_caret(left, right, op)?,
+            TokenType::GreaterGreaterEqual => self.binary_greater_greater(left, right, op)?,
+            TokenType::LessLessEqual => self.binary_less_less(left, right, op)?,
             _ => return Err(()),
         };
 
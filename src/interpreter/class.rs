@@ -0,0 +1,330 @@
+use super::content::Content;
+use super::environment::Environment;
+use super::function::Function;
+use super::interface::Interface;
+use super::signal::Signal;
+use crate::parser::statement::{Field, Signature};
+use crate::parser::types::Type;
+use crate::tokenizer::token::Token;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::mem;
+use std::rc::Rc;
+
+thread_local! {
+    // Instances waiting for their destructor to be called by the interpreter.
+    pub static DESTROYED: RefCell<Vec<Instance>> = const { RefCell::new(Vec::new()) };
+
+    // Instances being printed, so cycles between them are shown only once.
+    static PRINTING: RefCell<HashSet<usize>> = RefCell::new(HashSet::new());
+}
+
+#[derive(PartialEq)]
+pub enum Kind {
+    Struct,
+    Class,
+}
+
+// Structs are classes without methods nor superclass.
+pub struct Class {
+    pub id: Token,
+    pub kind: Kind,
+    pub superclass: Option<Rc<Class>>,
+    pub interfaces: Vec<Rc<Interface>>, // Only the interfaces declared by this class.
+    pub fields: Vec<Field>,             // Only the instance fields declared by this class.
+    pub methods: HashMap<String, Rc<Function>>,
+    pub statics: RefCell<HashMap<String, Content>>,
+    pub static_methods: HashMap<String, Rc<Function>>,
+    pub public: HashSet<String>, // Members visible outside of the class.
+    pub getters: HashMap<String, Rc<Function>>, // By field name.
+    pub setters: HashMap<String, Rc<Function>>, // By field name.
+    pub signals: Vec<Signature>, // Only the signals declared by this class.
+    pub types: HashMap<String, Type>, // By field name, including static fields.
+    pub closure: Rc<RefCell<Environment>>,
+}
+
+pub struct Instance {
+    pub class: Rc<Class>,
+    pub values: HashMap<String, Content>,
+    pub signals: HashMap<String, Rc<Signal>>,
+    pub destroyed: bool, // Destructor already called or scheduled.
+}
+
+impl Class {
+    pub fn has_field(&self, name: &str) -> bool {
+        match self.fields.iter().any(|f| f.id.lexeme == name) {
+            true => true,
+            false => match &self.superclass {
+                Some(s) => s.has_field(name),
+                None => false,
+            },
+        }
+    }
+
+    // Inherited fields come first.
+    pub fn field_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = match &self.superclass {
+            Some(s) => s.field_names(),
+            None => Vec::new(),
+        };
+
+        for field in &self.fields {
+            names.push(field.id.lexeme.clone());
+        }
+
+        names
+    }
+
+    pub fn find_type(&self, name: &str) -> Option<Type> {
+        match self.types.get(name) {
+            Some(t) => Some(t.clone()),
+            None => match &self.superclass {
+                Some(s) => s.find_type(name),
+                None => None,
+            },
+        }
+    }
+
+    pub fn find_method(&self, name: &str) -> Option<Rc<Function>> {
+        match self.methods.get(name) {
+            Some(m) => Some(Rc::clone(m)),
+            None => match &self.superclass {
+                Some(s) => s.find_method(name),
+                None => None,
+            },
+        }
+    }
+
+    pub fn declares(&self, name: &str) -> bool {
+        self.fields.iter().any(|f| f.id.lexeme == name)
+            || self.methods.contains_key(name)
+            || self.statics.borrow().contains_key(name)
+            || self.static_methods.contains_key(name)
+    }
+
+    // Class in the chain that declares the member.
+    pub fn owner(&self, name: &str) -> Option<&Class> {
+        match self.declares(name) {
+            true => Some(self),
+            false => match &self.superclass {
+                Some(s) => s.owner(name),
+                None => None,
+            },
+        }
+    }
+
+    // Members that aren't public are only visible inside their class and subclasses.
+    pub fn is_visible(&self, name: &str, context: Option<&Class>) -> bool {
+        match (self.owner(name), context) {
+            (Some(o), _) if o.public.contains(name) => true,
+            (Some(o), Some(c)) => c.is_subclass_of(o),
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+
+    pub fn find_static(&self, name: &str) -> Option<Content> {
+        match self.statics.borrow().get(name) {
+            Some(c) => Some(c.clone()),
+            None => match &self.superclass {
+                Some(s) => s.find_static(name),
+                None => None,
+            },
+        }
+    }
+
+    // Give back the replaced value.
+    pub fn set_static(&self, name: &str, value: Content) -> Option<Content> {
+        match self.statics.borrow_mut().get_mut(name) {
+            Some(c) => return Some(mem::replace(c, value)),
+            None => (),
+        }
+
+        match &self.superclass {
+            Some(s) => s.set_static(name, value),
+            None => None,
+        }
+    }
+
+    pub fn find_static_method(&self, name: &str) -> Option<Rc<Function>> {
+        match self.static_methods.get(name) {
+            Some(m) => Some(Rc::clone(m)),
+            None => match &self.superclass {
+                Some(s) => s.find_static_method(name),
+                None => None,
+            },
+        }
+    }
+
+    pub fn find_getter(&self, name: &str) -> Option<Rc<Function>> {
+        match self.getters.get(name) {
+            Some(g) => Some(Rc::clone(g)),
+            None => match &self.superclass {
+                Some(s) => s.find_getter(name),
+                None => None,
+            },
+        }
+    }
+
+    pub fn find_setter(&self, name: &str) -> Option<Rc<Function>> {
+        match self.setters.get(name) {
+            Some(s) => Some(Rc::clone(s)),
+            None => match &self.superclass {
+                Some(s) => s.find_setter(name),
+                None => None,
+            },
+        }
+    }
+
+    // Signals for a new instance, including the inherited ones.
+    pub fn create_signals(&self) -> HashMap<String, Rc<Signal>> {
+        let mut signals: HashMap<String, Rc<Signal>> = match &self.superclass {
+            Some(s) => s.create_signals(),
+            None => HashMap::new(),
+        };
+
+        for signature in &self.signals {
+            let signal: Signal = Signal {
+                id: signature.id.clone(),
+                params: signature.params.clone(),
+                handlers: RefCell::new(Vec::new()),
+                waiting: RefCell::new(Vec::new()),
+            };
+
+            signals.insert(signature.id.lexeme.clone(), Rc::new(signal));
+        }
+
+        signals
+    }
+
+    pub fn is_subclass_of(&self, class: &Class) -> bool {
+        match std::ptr::eq(self, class) {
+            true => true,
+            false => match &self.superclass {
+                Some(s) => s.is_subclass_of(class),
+                None => false,
+            },
+        }
+    }
+
+    pub fn implements(&self, interface: &Interface) -> bool {
+        match self
+            .interfaces
+            .iter()
+            .any(|i| std::ptr::eq(&**i, interface))
+        {
+            true => true,
+            false => match &self.superclass {
+                Some(s) => s.implements(interface),
+                None => false,
+            },
+        }
+    }
+
+    pub fn type_to_string(&self) -> String {
+        match self.kind {
+            Kind::Struct => "struct".to_string(),
+            Kind::Class => "class".to_string(),
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        format!("<{} {}>", self.type_to_string(), self.id.lexeme)
+    }
+}
+
+impl Instance {
+    pub fn get(&self, name: &str) -> Option<Content> {
+        self.values.get(name).cloned()
+    }
+
+    // Give back the replaced value.
+    pub fn set(&mut self, name: &str, value: Content) -> Option<Content> {
+        let field: &mut Content = self.values.get_mut(name)?;
+        Some(mem::replace(field, value))
+    }
+
+    // Take out the values, with the last declared field first. The fields are left
+    // as null, since a destructor may have kept the instance.
+    pub fn release(&mut self) -> Vec<Content> {
+        let mut values: Vec<Content> = Vec::new();
+
+        for name in self.class.field_names().iter().rev() {
+            match self.values.get_mut(name) {
+                Some(v) => values.push(mem::replace(v, Content::Null)),
+                None => (),
+            }
+        }
+
+        self.signals.clear();
+        values
+    }
+
+    // Only the fields visible from the context are shown.
+    pub fn to_string(&self, context: Option<&Class>) -> String {
+        let address: usize = self as *const Instance as usize;
+
+        if !PRINTING.with(|p| p.borrow_mut().insert(address)) {
+            return format!("{}(...)", self.class.id.lexeme);
+        }
+
+        let fields: Vec<String> = self
+            .class
+            .field_names()
+            .iter()
+            .filter(|f| self.class.is_visible(f, context))
+            .map(|f| format!("{}={}", f, self.values[f].to_string_in(context)))
+            .collect();
+
+        PRINTING.with(|p| p.borrow_mut().remove(&address));
+        format!("{}({})", self.class.id.lexeme, fields.join(", "))
+    }
+}
+
+// Classes and instances are only equal to themselves.
+impl PartialEq for Class {
+    fn eq(&self, other: &Class) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl PartialEq for Instance {
+    fn eq(&self, other: &Instance) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+// Dropping the last reference schedules the destructor, which revives the instance
+// for its duration. Fields are released after it, in reverse order of declaration.
+impl Drop for Instance {
+    fn drop(&mut self) {
+        if !self.destroyed && self.class.find_method("destructor").is_some() {
+            let instance: Instance = Instance {
+                class: Rc::clone(&self.class),
+                values: mem::take(&mut self.values),
+                signals: mem::take(&mut self.signals),
+                destroyed: true,
+            };
+
+            let _ = DESTROYED.try_with(|d| d.borrow_mut().push(instance));
+            return;
+        }
+
+        for name in self.class.field_names().iter().rev() {
+            self.values.remove(name);
+        }
+    }
+}
+
+impl fmt::Debug for Class {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+impl fmt::Debug for Instance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<instance {}>", self.class.id.lexeme)
+    }
+}
@@ -0,0 +1,373 @@
+use super::class::{Class, Instance};
+use super::content::Content;
+use super::coroutine::Coroutine;
+use super::environment::Environment;
+use super::function::Function;
+use super::native::Native;
+use super::signal::Signal;
+use std::cell::{Ref, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::rc::{Rc, Weak};
+
+// Functions and classes keep their declaration scope alive, while the scope keeps them.
+// When leaving a scope, it is released if nothing outside of these cycles can reach it.
+// Instances referencing each other are collected the same way, in batches, once they
+// are released.
+
+// Candidates kept before collecting, so long chains of instances are not searched again
+// each time one of them is released.
+const CANDIDATES_LIMIT: usize = 1000;
+
+thread_local! {
+    // Released instances which are still referenced, maybe only by cycles.
+    static CANDIDATES: RefCell<Vec<Weak<RefCell<Instance>>>> = const { RefCell::new(Vec::new()) };
+
+    // Instances in unreachable cycles waiting for their destructor to be called by the
+    // interpreter, which releases their fields afterwards to break the cycle.
+    pub static COLLECTED: RefCell<Vec<Rc<RefCell<Instance>>>> = const { RefCell::new(Vec::new()) };
+}
+
+enum Object {
+    Environment(Rc<RefCell<Environment>>),
+    Function(Rc<Function>),
+    Class(Rc<Class>),
+    Instance(Rc<RefCell<Instance>>),
+    Signal(Rc<Signal>),
+    Native(Rc<Native>),
+    Coroutine(Rc<Coroutine>),
+}
+
+struct Node {
+    object: Object,
+    internal: usize, // References coming from other nodes.
+    children: Vec<usize>,
+}
+
+struct Graph {
+    nodes: HashMap<usize, Node>,
+    order: Vec<usize>,         // Addresses by order of discovery.
+    reachable: HashSet<usize>, // Addresses reachable from outside of the graph.
+}
+
+impl Object {
+    fn address(&self) -> usize {
+        match self {
+            Object::Environment(e) => Rc::as_ptr(e) as *const () as usize,
+            Object::Function(f) => Rc::as_ptr(f) as *const () as usize,
+            Object::Class(c) => Rc::as_ptr(c) as *const () as usize,
+            Object::Instance(i) => Rc::as_ptr(i) as *const () as usize,
+            Object::Signal(s) => Rc::as_ptr(s) as *const () as usize,
+            Object::Native(n) => Rc::as_ptr(n) as *const () as usize,
+            Object::Coroutine(c) => Rc::as_ptr(c) as *const () as usize,
+        }
+    }
+
+    fn strong_count(&self) -> usize {
+        match self {
+            Object::Environment(e) => Rc::strong_count(e),
+            Object::Function(f) => Rc::strong_count(f),
+            Object::Class(c) => Rc::strong_count(c),
+            Object::Instance(i) => Rc::strong_count(i),
+            Object::Signal(s) => Rc::strong_count(s),
+            Object::Native(n) => Rc::strong_count(n),
+            Object::Coroutine(c) => Rc::strong_count(c),
+        }
+    }
+
+    fn children(&self) -> Vec<Object> {
+        let mut children: Vec<Object> = Vec::new();
+
+        match self {
+            Object::Environment(e) => {
+                let environment: Ref<Environment> = e.borrow();
+
+                for c in environment.contents() {
+                    content_children(c, &mut children);
+                }
+
+                match &environment.enclosing {
+                    Some(e) => children.push(Object::Environment(Rc::clone(e))),
+                    None => (),
+                }
+            }
+            Object::Function(f) => {
+                children.push(Object::Environment(Rc::clone(&f.closure)));
+
+                match &f.bound {
+                    Some(b) => content_children(b, &mut children),
+                    None => (),
+                }
+            }
+            Object::Class(c) => {
+                children.push(Object::Environment(Rc::clone(&c.closure)));
+
+                match &c.superclass {
+                    Some(s) => children.push(Object::Class(Rc::clone(s))),
+                    None => (),
+                }
+
+                let methods = c.methods.values().chain(c.static_methods.values());
+
+                for f in methods.chain(c.getters.values()).chain(c.setters.values()) {
+                    children.push(Object::Function(Rc::clone(f)));
+                }
+
+                for s in c.statics.borrow().values() {
+                    content_children(s, &mut children);
+                }
+            }
+            Object::Instance(i) => {
+                let instance: Ref<Instance> = i.borrow();
+                children.push(Object::Class(Rc::clone(&instance.class)));
+
+                for c in instance.values.values() {
+                    content_children(c, &mut children);
+                }
+
+                for s in instance.signals.values() {
+                    children.push(Object::Signal(Rc::clone(s)));
+                }
+            }
+            Object::Signal(s) => {
+                for h in s.handlers.borrow().iter() {
+                    content_children(h, &mut children);
+                }
+
+                for c in s.waiting.borrow().iter() {
+                    children.push(Object::Coroutine(Rc::clone(c)));
+                }
+            }
+            Object::Native(n) => content_children(&n.receiver, &mut children),
+            Object::Coroutine(c) => {
+                match &*c.environment.borrow() {
+                    Some(e) => children.push(Object::Environment(Rc::clone(e))),
+                    None => (),
+                }
+
+                for a in c.awaiters.borrow().iter() {
+                    children.push(Object::Coroutine(Rc::clone(a)));
+                }
+
+                content_children(&c.result.borrow(), &mut children);
+            }
+        }
+
+        children
+    }
+}
+
+fn content_children(content: &Content, children: &mut Vec<Object>) {
+    match content {
+        Content::Function(f) => children.push(Object::Function(Rc::clone(f))),
+        Content::Class(c) => children.push(Object::Class(Rc::clone(c))),
+        Content::Instance(i) => children.push(Object::Instance(Rc::clone(i))),
+        Content::Singleton(s) => {
+            children.push(Object::Class(Rc::clone(&s.class)));
+
+            match &*s.instance.borrow() {
+                Some(i) => content_children(i, children),
+                None => (),
+            }
+        }
+        Content::Signal(s) => children.push(Object::Signal(Rc::clone(s))),
+        Content::Native(n) => children.push(Object::Native(Rc::clone(n))),
+        Content::Coroutine(c) => children.push(Object::Coroutine(Rc::clone(c))),
+        Content::Module(m) => children.push(Object::Environment(Rc::clone(&m.environment))),
+        Content::Variant(v) | Content::Constructor(v) => {
+            for c in &v.values {
+                content_children(c, children);
+            }
+        }
+        _ => (),
+    }
+}
+
+// Release the values of a scope that is no longer reachable.
+pub fn release(environment: Rc<RefCell<Environment>>) {
+    if Rc::strong_count(&environment) > 1 && is_reachable(&environment) {
+        return;
+    }
+
+    let values: Vec<Content> = environment.borrow_mut().release();
+    drop(environment);
+    collect(values);
+}
+
+// Release the values even if the scope is still reachable, as when the program ends.
+pub fn release_all(environment: Rc<RefCell<Environment>>) {
+    let values: Vec<Content> = environment.borrow_mut().release();
+    drop(environment);
+    collect(values);
+}
+
+// Release the scope of a function, or the instances of a cycle, that may have lost
+// their last reference.
+pub fn dispose(content: Content) {
+    match content {
+        Content::Function(f) => {
+            let closure: Rc<RefCell<Environment>> = Rc::clone(&f.closure);
+            drop(f);
+            release(closure);
+        }
+        Content::Instance(_) => collect(vec![content]),
+        _ => (),
+    }
+}
+
+// Drop the values in order, keeping aside instances that outlive them as candidates.
+fn collect(values: Vec<Content>) {
+    for value in values {
+        match value {
+            Content::Instance(i) if Rc::strong_count(&i) > 1 => {
+                let _ = CANDIDATES.try_with(|c| c.borrow_mut().push(Rc::downgrade(&i)));
+            }
+            _ => (),
+        }
+    }
+
+    if CANDIDATES.try_with(|c| c.borrow().len()).unwrap_or(0) >= CANDIDATES_LIMIT {
+        collect_cycles();
+    }
+}
+
+// Check if only cycles between unreachable instances keep the candidates alive.
+pub fn collect_cycles() {
+    let weak: Vec<Weak<RefCell<Instance>>> = CANDIDATES
+        .try_with(|c| mem::take(&mut *c.borrow_mut()))
+        .unwrap_or_default();
+    let mut held: HashSet<usize> = HashSet::new();
+    let mut starts: Vec<Object> = Vec::new();
+
+    for w in weak {
+        match w.upgrade() {
+            Some(i) if !i.borrow().destroyed => {
+                if held.insert(Rc::as_ptr(&i) as *const () as usize) {
+                    starts.push(Object::Instance(i));
+                }
+            }
+            _ => (),
+        }
+    }
+
+    if starts.is_empty() {
+        return;
+    }
+
+    let graph: Graph = search(starts, &HashSet::new(), &held);
+
+    // The first unreachable instance is the one released, so its destructor runs first.
+    for address in &graph.order {
+        if graph.reachable.contains(address) {
+            continue;
+        }
+
+        match &graph.nodes[address].object {
+            Object::Instance(i) if !i.borrow().destroyed => {
+                i.borrow_mut().destroyed = true;
+                let _ = COLLECTED.try_with(|c| c.borrow_mut().push(Rc::clone(i)));
+            }
+            _ => (),
+        }
+    }
+}
+
+fn is_reachable(environment: &Rc<RefCell<Environment>>) -> bool {
+    // Enclosing scopes are still in use, so they are left out of the search.
+    let mut enclosing: HashSet<usize> = HashSet::new();
+    let mut e: Option<Rc<RefCell<Environment>>> = environment.borrow().enclosing.clone();
+
+    while let Some(current) = e {
+        enclosing.insert(Rc::as_ptr(&current) as *const () as usize);
+        e = current.borrow().enclosing.clone();
+    }
+
+    let start: Object = Object::Environment(Rc::clone(environment));
+    let start_address: usize = start.address();
+    let held: HashSet<usize> = HashSet::from([start_address]);
+
+    search(vec![start], &enclosing, &held)
+        .reachable
+        .contains(&start_address)
+}
+
+// Find the objects reachable from the starts, and which of them are also reachable
+// from outside of them. Objects in held have one more reference kept by the caller.
+fn search(starts: Vec<Object>, excluded: &HashSet<usize>, held: &HashSet<usize>) -> Graph {
+    let mut nodes: HashMap<usize, Node> = HashMap::new();
+    let mut order: Vec<usize> = Vec::new();
+
+    for start in starts {
+        let start_address: usize = start.address();
+        order.push(start_address);
+        nodes.insert(
+            start_address,
+            Node {
+                object: start,
+                internal: 0,
+                children: Vec::new(),
+            },
+        );
+    }
+
+    let mut pending: Vec<usize> = order.iter().rev().copied().collect();
+
+    while let Some(address) = pending.pop() {
+        let children: Vec<Object> = nodes[&address].object.children();
+        let mut addresses: Vec<usize> = Vec::new();
+
+        for child in children {
+            let child_address: usize = child.address();
+
+            if excluded.contains(&child_address) {
+                continue;
+            }
+
+            match nodes.get_mut(&child_address) {
+                Some(n) => n.internal += 1,
+                None => {
+                    nodes.insert(
+                        child_address,
+                        Node {
+                            object: child,
+                            internal: 1,
+                            children: Vec::new(),
+                        },
+                    );
+                    order.push(child_address);
+                    pending.push(child_address);
+                }
+            }
+
+            addresses.push(child_address);
+        }
+
+        match nodes.get_mut(&address) {
+            Some(n) => n.children = addresses,
+            None => (),
+        }
+    }
+
+    // Each node is also referenced once by the search.
+    let mut roots: Vec<usize> = nodes
+        .iter()
+        .filter(|(a, n)| {
+            let search: usize = if held.contains(*a) { 2 } else { 1 };
+            n.object.strong_count() > n.internal + search
+        })
+        .map(|(a, _)| *a)
+        .collect();
+    let mut reachable: HashSet<usize> = HashSet::new();
+
+    while let Some(address) = roots.pop() {
+        if reachable.insert(address) {
+            roots.extend(&nodes[&address].children);
+        }
+    }
+
+    Graph {
+        nodes: nodes,
+        order: order,
+        reachable: reachable,
+    }
+}
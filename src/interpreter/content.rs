@@ -1,4 +1,16 @@
+use super::class::{Class, Instance};
+use super::coroutine::Coroutine;
+use super::enumeration::{Enum, Variant};
+use super::function::Function;
+use super::interface::Interface;
+use super::module::Module;
+use super::native::Native;
+use super::range::Range;
+use super::signal::Signal;
+use super::singleton::Singleton;
 use crate::tokenizer::token_type::TokenType;
+use std::cell::RefCell;
+use std::rc::Rc;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Content {
@@ -7,6 +19,19 @@ pub enum Content {
     Floating(f32),
     Character(char),
     String_(String),
+    Range(Range),
+    Function(Rc<Function>),
+    Enum(Rc<Enum>),
+    Variant(Variant),
+    Constructor(Variant), // Variant still waiting for its values.
+    Class(Rc<Class>),     // Also used by structs.
+    Instance(Rc<RefCell<Instance>>),
+    Interface(Rc<Interface>),
+    Singleton(Rc<Singleton>), // Only lives in the global scope.
+    Signal(Rc<Signal>),
+    Native(Rc<Native>),
+    Coroutine(Rc<Coroutine>),
+    Module(Rc<Module>),
     Null,
 }
 
@@ -30,17 +55,48 @@ impl Content {
             Content::Floating(_) => "floating".to_string(),
             Content::Character(_) => "char".to_string(),
             Content::String_(_) => "string".to_string(),
+            Content::Range(_) => "range".to_string(),
+            Content::Function(_) => "function".to_string(),
+            Content::Enum(_) => "enum".to_string(),
+            Content::Variant(v) => v.enumeration.id.lexeme.clone(),
+            Content::Constructor(_) => "constructor".to_string(),
+            Content::Class(c) => c.type_to_string(),
+            Content::Instance(i) => i.borrow().class.id.lexeme.clone(),
+            Content::Interface(_) => "interface".to_string(),
+            Content::Singleton(_) => "singleton".to_string(),
+            Content::Signal(_) => "signal".to_string(),
+            Content::Native(_) => "function".to_string(),
+            Content::Coroutine(_) => "coroutine".to_string(),
+            Content::Module(_) => "module".to_string(),
             Content::Null => "null".to_string(),
         }
     }
 
     pub fn to_string(&self) -> String {
+        self.to_string_in(None)
+    }
+
+    // Instances only show the fields visible from the context.
+    pub fn to_string_in(&self, context: Option<&Class>) -> String {
         match self {
             Content::Boolean(b) => format!("{}", b),
             Content::Integer(i) => format!("{}", i),
             Content::Floating(f) => format!("{}", f),
             Content::Character(c) => format!("{}", c),
             Content::String_(s) => format!("{}", *s),
+            Content::Range(r) => r.to_string(),
+            Content::Function(f) => f.to_string(),
+            Content::Enum(e) => e.to_string(),
+            Content::Variant(v) => v.to_string_in(context),
+            Content::Constructor(v) => format!("<constructor {}>", v.to_string_in(context)),
+            Content::Class(c) => c.to_string(),
+            Content::Instance(i) => i.borrow().to_string(context),
+            Content::Interface(i) => i.to_string(),
+            Content::Singleton(s) => s.to_string(),
+            Content::Signal(s) => s.to_string(),
+            Content::Native(n) => n.to_string(),
+            Content::Coroutine(c) => c.to_string(),
+            Content::Module(m) => m.to_string(),
             Content::Null => "null".to_string(),
         }
     }
@@ -0,0 +1,128 @@
+use super::class::Class;
+use super::content::Content;
+use super::environment::Environment;
+use super::function::Function;
+use super::iteration::Iteration;
+use crate::parser::statement::Statement;
+use crate::parser::types::Type;
+use crate::tokenizer::token::Token;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum State {
+    Suspended,
+    Running,
+    Waiting, // Left to the scheduler, until what it awaits is done.
+    Finished,
+}
+
+// Statements that a coroutine is in the middle of, so it can stop and continue later.
+pub enum Frame {
+    Block {
+        block: Rc<Statement>,
+        index: usize, // Next statement to run.
+    },
+    Scope, // Loop variable or pattern bindings.
+    While {
+        stmt: Rc<Statement>,
+    },
+    Loop {
+        statement: Rc<Statement>,
+    },
+    For {
+        id: Token,
+        iteration: Iteration,
+        statement: Rc<Statement>,
+    },
+}
+
+// Why a coroutine stopped running.
+pub enum Stop {
+    Yield(Content),
+    Await(Content, Option<Token>), // What is awaited and the variable receiving its value.
+    Return(Content),
+}
+
+pub struct Coroutine {
+    pub id: Token,
+    pub owner: Option<Weak<Class>>,
+    pub output: Option<Type>, // Type of the returned value.
+    pub state: Cell<State>,
+    pub environment: RefCell<Option<Rc<RefCell<Environment>>>>, // Scope where it stopped.
+    pub frames: RefCell<Vec<Frame>>,
+    pub target: RefCell<Option<Token>>, // Variable declared by the "await" it stopped at.
+    pub result: RefCell<Content>,       // Value returned when it finished.
+    pub awaiters: RefCell<Vec<Rc<Coroutine>>>,
+}
+
+impl Frame {
+    pub fn is_loop(&self) -> bool {
+        match self {
+            Frame::While { stmt: _ } | Frame::Loop { statement: _ } => true,
+            Frame::For {
+                id: _,
+                iteration: _,
+                statement: _,
+            } => true,
+            _ => false,
+        }
+    }
+}
+
+impl Coroutine {
+    // Suspended before the first statement of the body.
+    pub fn new(function: &Function, arguments: Vec<Content>) -> Coroutine {
+        let mut environment: Environment = Environment::from(&function.closure);
+
+        for (param, arg) in function.params.iter().zip(arguments) {
+            environment.define_typed(&param.id, arg, &param.annotation);
+        }
+
+        let environment: Rc<RefCell<Environment>> = Rc::new(RefCell::new(environment));
+        let body: Environment = Environment::from(&environment);
+        let frame: Frame = Frame::Block {
+            block: Rc::clone(&function.body),
+            index: 0,
+        };
+
+        Coroutine {
+            id: function.id.clone(),
+            owner: function.owner.clone(),
+            output: function.output.clone(),
+            state: Cell::new(State::Suspended),
+            environment: RefCell::new(Some(Rc::new(RefCell::new(body)))),
+            frames: RefCell::new(vec![frame]),
+            target: RefCell::new(None),
+            result: RefCell::new(Content::Null),
+            awaiters: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn state_to_string(&self) -> String {
+        match self.state.get() {
+            State::Suspended => "suspended".to_string(),
+            State::Running => "running".to_string(),
+            State::Waiting => "waiting".to_string(),
+            State::Finished => "finished".to_string(),
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        format!("<coro {} {}>", self.id.lexeme, self.state_to_string())
+    }
+}
+
+// Coroutines are only equal to themselves.
+impl PartialEq for Coroutine {
+    fn eq(&self, other: &Coroutine) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl fmt::Debug for Coroutine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
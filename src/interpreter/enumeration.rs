@@ -0,0 +1,74 @@
+use super::class::Class;
+use super::content::Content;
+use crate::parser::statement::EnumVariant;
+use crate::tokenizer::token::Token;
+use std::fmt;
+use std::rc::Rc;
+
+pub struct Enum {
+    pub id: Token,
+    pub variants: Vec<EnumVariant>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub enumeration: Rc<Enum>,
+    pub name: String,
+    pub values: Vec<Content>,
+}
+
+impl Enum {
+    pub fn get_variant(&self, name: &str) -> Option<&EnumVariant> {
+        self.variants.iter().find(|v| v.id.lexeme == name)
+    }
+
+    pub fn to_string(&self) -> String {
+        format!("<enum {}>", self.id.lexeme)
+    }
+}
+
+// Enums are only equal to themselves.
+impl PartialEq for Enum {
+    fn eq(&self, other: &Enum) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl fmt::Debug for Enum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+impl Variant {
+    // Number of values the variant carries.
+    pub fn arity(&self) -> usize {
+        match self.enumeration.get_variant(&self.name) {
+            Some(v) => v.fields.len(),
+            None => 0,
+        }
+    }
+
+    pub fn is_same_variant(&self, other: &Variant) -> bool {
+        self.enumeration == other.enumeration && self.name == other.name
+    }
+
+    pub fn to_string(&self) -> String {
+        self.to_string_in(None)
+    }
+
+    pub fn to_string_in(&self, context: Option<&Class>) -> String {
+        let name: String = format!("{}.{}", self.enumeration.id.lexeme, self.name);
+
+        if self.values.is_empty() {
+            return name;
+        }
+
+        let values: Vec<String> = self
+            .values
+            .iter()
+            .map(|v| v.to_string_in(context))
+            .collect();
+        format!("{}({})", name, values.join(", "))
+    }
+}
@@ -1,31 +1,41 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
 use super::content::Content;
 use super::error::*;
+use super::utility::mismatch;
 use crate::error::interpreter_error;
+use crate::parser::types::Type;
 use crate::tokenizer::token::Token;
 
-// I'm thinking in transforming in Enum, so I wouldn't need Option<>.
-#[derive(Clone)]
+// Scopes are shared, so closures can keep their defining scope alive.
 pub struct Environment {
-    // I'm thinking in naming vars/consts/etc
-    // Making a HashMap for each, this would MAY help me control who can be reassigned.
     values: HashMap<String, Content>,
-    pub enclosing: Option<Box<Environment>>,
+    names: Vec<String>,           // Names in values by order of declaration.
+    constants: HashSet<String>,   // Names in values that can't be reassigned.
+    types: HashMap<String, Type>, // Names in values that only accept one type.
+    pub enclosing: Option<Rc<RefCell<Environment>>>,
 }
 
 impl Environment {
     pub fn new() -> Environment {
         Environment {
             values: HashMap::new(),
+            names: Vec::new(),
+            constants: HashSet::new(),
+            types: HashMap::new(),
             enclosing: None,
         }
     }
 
-    pub fn from(environment: Environment) -> Environment {
+    pub fn from(environment: &Rc<RefCell<Environment>>) -> Environment {
         Environment {
             values: HashMap::new(),
-            enclosing: Some(Box::new(environment)),
+            names: Vec::new(),
+            constants: HashSet::new(),
+            types: HashMap::new(),
+            enclosing: Some(Rc::clone(environment)),
         }
     }
 
@@ -36,7 +46,7 @@ impl Environment {
         };
 
         match &self.enclosing {
-            Some(e) => (*e).get(token),
+            Some(e) => e.borrow().get(token),
             None => {
                 interpreter_error(token.line, variable_undefined(&token.lexeme.to_string()));
                 Err(())
@@ -44,18 +54,94 @@ impl Environment {
         }
     }
 
+    // Only this scope is searched, and a missing name is not an error.
+    pub fn lookup(&self, name: &str) -> Option<Content> {
+        self.values.get(name).cloned()
+    }
+
     pub fn define(&mut self, token: &Token, value: Content) {
-        self.values.insert(token.lexeme.clone(), value);
+        self.insert(token, value);
+    }
+
+    // Declaring a name again in the same scope replaces it, which constants don't allow.
+    pub fn check_declaration(&self, token: &Token) -> Result<(), ()> {
+        match self.constants.contains(&token.lexeme) {
+            true => {
+                interpreter_error(token.line, constant_redeclared(&token.lexeme));
+                Err(())
+            }
+            false => Ok(()),
+        }
+    }
+
+    pub fn define_constant(&mut self, token: &Token, value: Content) {
+        self.constants.insert(token.lexeme.clone());
+        self.insert(token, value);
+    }
+
+    // Later assignments must keep the type.
+    pub fn define_typed(&mut self, token: &Token, value: Content, annotation: &Option<Type>) {
+        self.define(token, value);
+
+        match annotation {
+            Some(t) => {
+                self.types.insert(token.lexeme.clone(), t.clone());
+            }
+            None => (),
+        }
     }
 
+    pub fn contents(&self) -> impl Iterator<Item = &Content> {
+        self.values.values()
+    }
+
+    // Take out the values, with the last declared first.
+    pub fn release(&mut self) -> Vec<Content> {
+        let mut values: Vec<Content> = Vec::new();
+
+        for name in self.names.drain(..).rev() {
+            match self.values.remove(&name) {
+                Some(v) => values.push(v),
+                None => (),
+            }
+        }
+
+        self.constants.clear();
+        self.types.clear();
+        values
+    }
+
+    fn insert(&mut self, token: &Token, value: Content) {
+        self.types.remove(&token.lexeme);
+
+        match self.values.insert(token.lexeme.clone(), value) {
+            Some(_) => (),
+            None => self.names.push(token.lexeme.clone()),
+        }
+    }
+
+    // Give back the replaced value.
     pub fn assign(&mut self, token: &Token, value: Content) -> Result<Content, ()> {
-        if self.values.contains_key(&token.lexeme) {
-            self.values.insert(token.lexeme.clone(), value);
-            return Ok(Content::Null);
+        if self.constants.contains(&token.lexeme) {
+            interpreter_error(token.line, constant_reassigned(&token.lexeme));
+            return Err(());
         }
 
-        match &mut self.enclosing {
-            Some(e) => (*e).assign(token, value),
+        match mismatch(&value, &self.types.get(&token.lexeme).cloned()) {
+            Some(t) => {
+                interpreter_error(token.line, variable_type_mismatch(&token.lexeme, t, &value));
+                return Err(());
+            }
+            None => (),
+        }
+
+        match self.values.get_mut(&token.lexeme) {
+            Some(v) => return Ok(std::mem::replace(v, value)),
+            None => (),
+        }
+
+        match &self.enclosing {
+            Some(e) => e.borrow_mut().assign(token, value),
             None => {
                 interpreter_error(token.line, variable_undefined(&token.lexeme.to_string()));
                 Err(())
@@ -63,3 +149,12 @@ impl Environment {
         }
     }
 }
+
+// Release values in reverse order of declaration, so destructors run in a known order.
+impl Drop for Environment {
+    fn drop(&mut self) {
+        for name in self.names.iter().rev() {
+            self.values.remove(name);
+        }
+    }
+}
@@ -1,9 +1,272 @@
 use super::content::Content;
+use crate::parser::types::Type;
+
+pub const YIELD_OUTSIDE_COROUTINE: &str = "Can't use 'yield' outside of a coroutine.";
+pub const AWAIT_OUTSIDE_COROUTINE: &str = "Can't use 'await' outside of a coroutine.";
+pub const STACK_OVERFLOW: &str = "Stack overflow.";
 
 pub fn variable_undefined(name: &str) -> String {
     format!("Undefined variable '{}'", name)
 }
 
+pub fn constant_reassigned(name: &str) -> String {
+    format!("Can't assign to constant '{}'", name)
+}
+
+pub fn constant_redeclared(name: &str) -> String {
+    format!("Name '{}' is already declared as a constant", name)
+}
+
+pub fn range_unsupported(c1: &Content, c2: &Content) -> String {
+    format!(
+        "Unsupported range for: {} and {}",
+        c1.type_to_string(),
+        c2.type_to_string()
+    )
+}
+
+pub fn range_invalid_step(c: &Content) -> String {
+    format!(
+        "Range step must be a non-zero integer, got: {}",
+        c.to_string()
+    )
+}
+
+pub fn not_iterable(c: &Content) -> String {
+    format!("Can't iterate over: {}", c.type_to_string())
+}
+
+pub fn not_callable(c: &Content) -> String {
+    format!("Can only call functions, got: {}", c.type_to_string())
+}
+
+pub fn not_signal(c: &Content) -> String {
+    format!("Can only emit signals, got: {}", c.type_to_string())
+}
+
+pub fn handler_invalid(c: &Content) -> String {
+    format!(
+        "Can only connect functions to signals, got: {}",
+        c.type_to_string()
+    )
+}
+
+pub fn handler_arity_mismatch(name: &str, expected: usize, got: usize) -> String {
+    format!(
+        "Handlers of signal '{}' must take {} arguments but got one taking {}",
+        name, expected, got
+    )
+}
+
+pub fn handler_connected(name: &str) -> String {
+    format!("Handler is already connected to signal '{}'", name)
+}
+
+pub fn handler_disconnected(name: &str) -> String {
+    format!("Handler isn't connected to signal '{}'", name)
+}
+
+pub fn iterator_invalid(c: &Content) -> String {
+    format!(
+        "Method 'iter' must return a range, string or coroutine, got: {}",
+        c.type_to_string()
+    )
+}
+
+pub fn not_coroutine(c: &Content) -> String {
+    format!("Can only resume coroutines, got: {}", c.type_to_string())
+}
+
+pub fn coroutine_running(name: &str) -> String {
+    format!("Coroutine '{}' is already running", name)
+}
+
+pub fn coroutine_waiting(name: &str) -> String {
+    format!(
+        "Coroutine '{}' is waiting and can only be continued by the scheduler",
+        name
+    )
+}
+
+pub fn await_invalid(c: &Content) -> String {
+    format!(
+        "Can only await signals and coroutines, got: {}",
+        c.type_to_string()
+    )
+}
+
+pub fn sleep_invalid(c: &Content) -> String {
+    format!(
+        "Expect a non-negative integer of milliseconds, got: {}",
+        c.to_string()
+    )
+}
+
+pub fn coroutine_finished(name: &str) -> String {
+    format!("Coroutine '{}' has already finished", name)
+}
+
+pub fn arity_mismatch(name: &str, expected: usize, got: usize) -> String {
+    format!("'{}' expects {} arguments but got {}", name, expected, got)
+}
+
+pub fn property_undefined(c: &Content, name: &str) -> String {
+    format!("Undefined property '{}' on: {}", name, c.to_string())
+}
+
+pub fn method_undefined(class: &str, name: &str) -> String {
+    format!("Undefined method '{}' on class '{}'", name, class)
+}
+
+pub fn superclass_invalid(c: &Content) -> String {
+    format!("Superclass must be a class, got: {}", c.to_string())
+}
+
+pub fn interface_invalid(c: &Content) -> String {
+    format!("Can only implement interfaces, got: {}", c.to_string())
+}
+
+pub fn interface_method_missing(class: &str, interface: &str, name: &str) -> String {
+    format!(
+        "Class '{}' is missing method '{}' from interface '{}'",
+        class, name, interface
+    )
+}
+
+pub fn interface_method_private(class: &str, interface: &str, name: &str) -> String {
+    format!(
+        "Method '{}' of class '{}' must be public to implement interface '{}'",
+        name, class, interface
+    )
+}
+
+pub fn member_private(class: &str, name: &str) -> String {
+    format!("Member '{}' of class '{}' is not public", name, class)
+}
+
+pub fn interface_arity_mismatch(
+    class: &str,
+    interface: &str,
+    name: &str,
+    expected: usize,
+    got: usize,
+) -> String {
+    format!(
+        "Method '{}' of class '{}' takes {} arguments but interface '{}' expects {}",
+        name, class, got, interface, expected
+    )
+}
+
+pub fn type_unsupported(c: &Content) -> String {
+    format!(
+        "Expect class, struct, enum or interface after 'is', got: {}",
+        c.to_string()
+    )
+}
+
+pub fn singleton_recursive(name: &str) -> String {
+    format!("Singleton '{}' was accessed during its own creation", name)
+}
+
+pub fn property_unsettable(c: &Content) -> String {
+    format!("Can't set properties on: {}", c.type_to_string())
+}
+
+pub fn named_arguments_unsupported(c: &Content) -> String {
+    format!(
+        "Named arguments are only supported by structs, got: {}",
+        c.to_string()
+    )
+}
+
+pub fn argument_duplicated(name: &str) -> String {
+    format!("Argument '{}' was given more than once", name)
+}
+
+pub fn no_match(c: &Content) -> String {
+    format!("No 'match' arm for value: {}", c.to_string())
+}
+
+pub fn pattern_arity_mismatch(name: &str, expected: usize, got: usize) -> String {
+    format!(
+        "Variant '{}' has {} fields but the pattern has {}",
+        name, expected, got
+    )
+}
+
+pub fn pattern_not_variant(c: &Content) -> String {
+    format!(
+        "Expect variant with fields in pattern, got: {}",
+        c.to_string()
+    )
+}
+
+pub fn module_not_found(name: &str, directories: &Vec<String>) -> String {
+    format!("Module '{}' not found in: {}", name, directories.join(", "))
+}
+
+pub fn module_unreadable(name: &str) -> String {
+    format!("Unable to read module '{}'", name)
+}
+
+pub fn module_invalid(name: &str) -> String {
+    format!("Module '{}' has errors", name)
+}
+
+pub fn module_failed(name: &str) -> String {
+    format!("Module '{}' failed to execute", name)
+}
+
+pub fn module_member_undefined(module: &str, name: &str) -> String {
+    format!("Module '{}' has no public member '{}'", module, name)
+}
+
+pub fn import_cycle(chain: &Vec<String>) -> String {
+    format!("Import cycle: {}", chain.join(" -> "))
+}
+
+pub fn variable_type_mismatch(name: &str, expected: &Type, got: &Content) -> String {
+    format!(
+        "Variable '{}' expects {}, got: {}",
+        name,
+        expected.to_string(),
+        annotation_name(got)
+    )
+}
+
+pub fn parameter_type_mismatch(
+    function: &str,
+    name: &str,
+    expected: &Type,
+    got: &Content,
+) -> String {
+    format!(
+        "Parameter '{}' of '{}' expects {}, got: {}",
+        name,
+        function,
+        expected.to_string(),
+        annotation_name(got)
+    )
+}
+
+pub fn return_type_mismatch(function: &str, expected: &Type, got: &Content) -> String {
+    format!(
+        "Function '{}' must return {}, got: {}",
+        function,
+        expected.to_string(),
+        annotation_name(got)
+    )
+}
+
+pub fn field_type_mismatch(name: &str, expected: &Type, got: &Content) -> String {
+    format!(
+        "Field '{}' expects {}, got: {}",
+        name,
+        expected.to_string(),
+        annotation_name(got)
+    )
+}
+
 pub fn unary_unsupported(op: &str, c: &Content) -> String {
     format!("Unsupported operator '{}' for: {}", op, c.type_to_string())
 }
@@ -16,3 +279,15 @@ pub fn binary_unsupported(op: &str, c1: &Content, c2: &Content) -> String {
         c2.type_to_string()
     )
 }
+
+// Values are named like the annotations they don't match.
+fn annotation_name(c: &Content) -> String {
+    match c {
+        Content::Boolean(_) => Type::Boolean.to_string(),
+        Content::Integer(_) => Type::Integer.to_string(),
+        Content::Floating(_) => Type::Floating.to_string(),
+        Content::Character(_) => Type::Character.to_string(),
+        Content::String_(_) => Type::String_.to_string(),
+        _ => c.type_to_string(),
+    }
+}
@@ -0,0 +1,9 @@
+use super::content::Content;
+
+// Tell the enclosing statements how execution should proceed.
+pub enum Flow {
+    Next,
+    Break,
+    Continue,
+    Return(Content),
+}
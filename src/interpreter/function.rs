@@ -0,0 +1,74 @@
+use super::class::Class;
+use super::content::Content;
+use super::environment::Environment;
+use crate::parser::statement::{Param, Statement};
+use crate::parser::types::Type;
+use crate::tokenizer::token::Token;
+use crate::tokenizer::token_type::TokenType;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+pub struct Function {
+    pub id: Token,
+    pub params: Vec<Param>,
+    pub output: Option<Type>, // Return type.
+    pub body: Rc<Statement>,
+    pub closure: Rc<RefCell<Environment>>,
+    pub owner: Option<Weak<Class>>, // Class declaring the method.
+    pub bound: Option<Content>,     // Instance referred by "self".
+    pub coroutine: bool,            // Calls create a coroutine instead of running.
+}
+
+impl Function {
+    pub fn arity(&self) -> usize {
+        self.params.len()
+    }
+
+    // Method copy where "self" refers to the instance.
+    pub fn bind(&self, instance: Content) -> Function {
+        let self_: Token = Token {
+            token_type: TokenType::Self_,
+            lexeme: "self".to_string(),
+            line: self.id.line,
+        };
+        let mut environment: Environment = Environment::from(&self.closure);
+        environment.define(&self_, instance.clone());
+
+        Function {
+            id: self.id.clone(),
+            params: self.params.clone(),
+            output: self.output.clone(),
+            body: Rc::clone(&self.body),
+            closure: Rc::new(RefCell::new(environment)),
+            owner: self.owner.clone(),
+            bound: Some(instance),
+            coroutine: self.coroutine,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        format!("<func {}>", self.id.lexeme)
+    }
+}
+
+// Functions are only equal to themselves,
+// except methods bound to the same instance more than once.
+impl PartialEq for Function {
+    fn eq(&self, other: &Function) -> bool {
+        if std::ptr::eq(self, other) {
+            return true;
+        }
+
+        match (&self.bound, &other.bound) {
+            (Some(b1), Some(b2)) => Rc::ptr_eq(&self.body, &other.body) && b1 == b2,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Debug for Function {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
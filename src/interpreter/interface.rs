@@ -0,0 +1,27 @@
+use crate::parser::statement::Signature;
+use crate::tokenizer::token::Token;
+use std::fmt;
+
+pub struct Interface {
+    pub id: Token,
+    pub signatures: Vec<Signature>,
+}
+
+impl Interface {
+    pub fn to_string(&self) -> String {
+        format!("<interface {}>", self.id.lexeme)
+    }
+}
+
+// Interfaces are only equal to themselves.
+impl PartialEq for Interface {
+    fn eq(&self, other: &Interface) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl fmt::Debug for Interface {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
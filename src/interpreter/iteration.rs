@@ -0,0 +1,10 @@
+use super::coroutine::Coroutine;
+use super::range::Range;
+use std::rc::Rc;
+
+// Where a "for" loop is in the content it goes through.
+pub enum Iteration {
+    Range { range: Range, n: i32 },
+    String_ { characters: Vec<char>, n: usize },
+    Coroutine(Rc<Coroutine>), // Resumed for each value until it finishes.
+}
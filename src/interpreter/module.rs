@@ -0,0 +1,41 @@
+use super::content::Content;
+use super::environment::Environment;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+pub struct Module {
+    pub id: String,
+    pub file: PathBuf,
+    pub environment: Rc<RefCell<Environment>>, // Global scope of the module.
+    pub public: HashSet<String>,               // Public top-level declarations.
+}
+
+impl Module {
+    // Members are read from the module scope, so they show its current values.
+    pub fn get(&self, name: &str) -> Option<Content> {
+        match self.public.contains(name) {
+            true => self.environment.borrow().lookup(name),
+            false => None,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        format!("<module {}>", self.id)
+    }
+}
+
+// Modules are cached, so each file has only one.
+impl PartialEq for Module {
+    fn eq(&self, other: &Module) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl fmt::Debug for Module {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
@@ -0,0 +1,31 @@
+use super::content::Content;
+use super::Interpreter;
+use crate::tokenizer::token::Token;
+use std::fmt;
+
+// Function implemented by the interpreter itself.
+pub struct Native {
+    pub id: String,
+    pub arity: usize,
+    pub receiver: Content, // Value the function was taken from.
+    pub function: fn(&mut Interpreter, &Native, Vec<Content>, &Token) -> Result<Content, ()>,
+}
+
+impl Native {
+    pub fn to_string(&self) -> String {
+        format!("<native {}>", self.id)
+    }
+}
+
+// Natives are equal when taken from the same value.
+impl PartialEq for Native {
+    fn eq(&self, other: &Native) -> bool {
+        self.id == other.id && self.receiver == other.receiver
+    }
+}
+
+impl fmt::Debug for Native {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
@@ -0,0 +1,67 @@
+use super::content::Content;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Range {
+    pub start: i32,
+    pub end: i32,
+    pub step: i32,
+    pub inclusive: bool,
+    pub character: bool, // Values are converted back to characters.
+}
+
+impl Range {
+    // Get the nth value of the range, or None if it passed the end.
+    pub fn nth(&self, n: i32) -> Option<Content> {
+        let value: i64 = self.start as i64 + n as i64 * self.step as i64;
+
+        let inside: bool = match (self.step > 0, self.inclusive) {
+            (true, true) => value <= self.end as i64,
+            (true, false) => value < self.end as i64,
+            (false, true) => value >= self.end as i64,
+            (false, false) => value > self.end as i64,
+        };
+
+        if !inside {
+            return None;
+        }
+
+        if self.character {
+            char::from_u32(value as u32).map(Content::Character)
+        } else {
+            Some(Content::Integer(value as i32))
+        }
+    }
+
+    // Check if the content is one of the values in the range.
+    pub fn contains(&self, content: &Content) -> bool {
+        let value: i64 = match (content, self.character) {
+            (Content::Integer(i), false) => *i as i64,
+            (Content::Character(c), true) => *c as i64,
+            _ => return false,
+        };
+
+        let offset: i64 = value - self.start as i64;
+        let n: i64 = offset / self.step as i64;
+
+        offset % self.step as i64 == 0 && n >= 0 && self.nth(n as i32).is_some()
+    }
+
+    pub fn to_string(&self) -> String {
+        let (start, end) = if self.character {
+            (
+                format!("'{}'", char::from_u32(self.start as u32).unwrap_or('\0')),
+                format!("'{}'", char::from_u32(self.end as u32).unwrap_or('\0')),
+            )
+        } else {
+            (format!("{}", self.start), format!("{}", self.end))
+        };
+
+        let op: &str = if self.inclusive { "..=" } else { ".." };
+
+        if self.step == 1 {
+            format!("{}{}{}", start, op, end)
+        } else {
+            format!("{}{}{}..{}", start, op, end, self.step)
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use super::content::Content;
+use super::coroutine::Coroutine;
+use super::error::*;
+use super::native::Native;
+use super::signal::Signal;
+use super::Interpreter;
+use crate::error::interpreter_error;
+use crate::tokenizer::token::Token;
+use crate::tokenizer::token_type::TokenType;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+// Coroutines that are waiting take turns after the script, one at a time.
+// Time is virtual: when nothing can run, it jumps straight to the next timer.
+pub struct Scheduler {
+    pub ready: VecDeque<(Rc<Coroutine>, Content)>, // With the value they were waiting for.
+    pub timers: Vec<(u64, Rc<Signal>)>,            // In creation order.
+    pub clock: u64,                                // Milliseconds since the start.
+}
+
+impl Scheduler {
+    pub fn new() -> Scheduler {
+        Scheduler {
+            ready: VecDeque::new(),
+            timers: Vec::new(),
+            clock: 0,
+        }
+    }
+
+    // Forget the coroutines and timers still waiting, after an error.
+    pub fn cancel(&mut self) {
+        self.ready.clear();
+        self.timers.clear();
+    }
+
+    // Timer that ends first, or that was created first when they end together.
+    pub fn next_timer(&mut self) -> Option<(u64, Rc<Signal>)> {
+        let mut next: Option<usize> = None;
+
+        for (i, (time, _)) in self.timers.iter().enumerate() {
+            match next {
+                Some(n) if self.timers[n].0 <= *time => (),
+                _ => next = Some(i),
+            }
+        }
+
+        next.map(|n| self.timers.remove(n))
+    }
+}
+
+pub fn natives() -> Vec<Native> {
+    vec![
+        Native {
+            id: "sleep".to_string(),
+            arity: 1,
+            receiver: Content::Null,
+            function: sleep,
+        },
+        Native {
+            id: "time".to_string(),
+            arity: 0,
+            receiver: Content::Null,
+            function: time,
+        },
+    ]
+}
+
+// Signal emitted once the milliseconds pass.
+fn sleep(
+    interpreter: &mut Interpreter,
+    _native: &Native,
+    arguments: Vec<Content>,
+    paren: &Token,
+) -> Result<Content, ()> {
+    let ms: u64 = match &arguments[0] {
+        Content::Integer(i) if *i >= 0 => *i as u64,
+        c => {
+            interpreter_error(paren.line, sleep_invalid(c));
+            return Err(());
+        }
+    };
+
+    let signal: Signal = Signal {
+        id: Token {
+            token_type: TokenType::Identifier("timeout".to_string()),
+            lexeme: "timeout".to_string(),
+            line: paren.line,
+        },
+        params: Vec::new(),
+        handlers: RefCell::new(Vec::new()),
+        waiting: RefCell::new(Vec::new()),
+    };
+    let signal: Rc<Signal> = Rc::new(signal);
+    let scheduler: &mut Scheduler = &mut interpreter.scheduler;
+
+    scheduler
+        .timers
+        .push((scheduler.clock + ms, Rc::clone(&signal)));
+    Ok(Content::Signal(signal))
+}
+
+fn time(
+    interpreter: &mut Interpreter,
+    _native: &Native,
+    _arguments: Vec<Content>,
+    _paren: &Token,
+) -> Result<Content, ()> {
+    Ok(Content::Integer(interpreter.scheduler.clock as i32))
+}
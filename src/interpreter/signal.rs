@@ -0,0 +1,125 @@
+use super::content::Content;
+use super::coroutine::Coroutine;
+use super::error::*;
+use super::native::Native;
+use super::Interpreter;
+use crate::error::interpreter_error;
+use crate::parser::statement::Param;
+use crate::tokenizer::token::Token;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+// Each instance has its own signals, created from the class declaration.
+pub struct Signal {
+    pub id: Token,
+    pub params: Vec<Param>,
+    pub handlers: RefCell<Vec<Content>>, // In connection order.
+    pub waiting: RefCell<Vec<Rc<Coroutine>>>, // Coroutines awaiting the next emission.
+}
+
+impl Signal {
+    pub fn arity(&self) -> usize {
+        self.params.len()
+    }
+
+    // Methods of signals, bound to the signal.
+    pub fn method(signal: &Rc<Signal>, name: &str) -> Option<Native> {
+        let function: fn(&mut Interpreter, &Native, Vec<Content>, &Token) -> Result<Content, ()> =
+            match name {
+                "connect" => connect,
+                "disconnect" => disconnect,
+                _ => return None,
+            };
+
+        Some(Native {
+            id: name.to_string(),
+            arity: 1,
+            receiver: Content::Signal(Rc::clone(signal)),
+            function: function,
+        })
+    }
+
+    pub fn to_string(&self) -> String {
+        format!("<signal {}>", self.id.lexeme)
+    }
+}
+
+fn connect(
+    _interpreter: &mut Interpreter,
+    native: &Native,
+    mut arguments: Vec<Content>,
+    paren: &Token,
+) -> Result<Content, ()> {
+    let signal: &Signal = match &native.receiver {
+        Content::Signal(s) => s,
+        _ => return Err(()),
+    };
+    let handler: Content = arguments.remove(0);
+
+    let arity: usize = match &handler {
+        Content::Function(f) => f.arity(),
+        Content::Native(n) => n.arity,
+        c => {
+            interpreter_error(paren.line, handler_invalid(c));
+            return Err(());
+        }
+    };
+
+    if arity != signal.arity() {
+        interpreter_error(
+            paren.line,
+            handler_arity_mismatch(&signal.id.lexeme, signal.arity(), arity),
+        );
+        return Err(());
+    }
+
+    if signal.handlers.borrow().contains(&handler) {
+        interpreter_error(paren.line, handler_connected(&signal.id.lexeme));
+        return Err(());
+    }
+
+    signal.handlers.borrow_mut().push(handler);
+    Ok(Content::Null)
+}
+
+fn disconnect(
+    _interpreter: &mut Interpreter,
+    native: &Native,
+    arguments: Vec<Content>,
+    paren: &Token,
+) -> Result<Content, ()> {
+    let signal: &Signal = match &native.receiver {
+        Content::Signal(s) => s,
+        _ => return Err(()),
+    };
+    let position: Option<usize> = signal
+        .handlers
+        .borrow()
+        .iter()
+        .position(|h| *h == arguments[0]);
+
+    match position {
+        Some(p) => {
+            signal.handlers.borrow_mut().remove(p);
+            Ok(Content::Null)
+        }
+        None => {
+            interpreter_error(paren.line, handler_disconnected(&signal.id.lexeme));
+            Err(())
+        }
+    }
+}
+
+// Signals are only equal to themselves.
+impl PartialEq for Signal {
+    fn eq(&self, other: &Signal) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl fmt::Debug for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
@@ -0,0 +1,30 @@
+use super::class::Class;
+use super::content::Content;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+
+pub struct Singleton {
+    pub class: Rc<Class>,
+    pub instance: RefCell<Option<Content>>, // Created on first access.
+    pub initializing: Cell<bool>,
+}
+
+impl Singleton {
+    pub fn to_string(&self) -> String {
+        format!("<singleton {}>", self.class.id.lexeme)
+    }
+}
+
+// Singletons are only equal to themselves.
+impl PartialEq for Singleton {
+    fn eq(&self, other: &Singleton) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl fmt::Debug for Singleton {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+
+// Modules bundled with the interpreter, so they are found wherever it is installed.
+const MODULES: [(&str, &str); 1] = [("math", include_str!("../../stdlib/math.lai"))];
+
+// Shown where a directory would be.
+pub const STDLIB: &str = "<stdlib>";
+
+// Path identifying the module and its code.
+pub fn find(id: &str) -> Option<(PathBuf, &'static str)> {
+    MODULES
+        .iter()
+        .find(|(name, _)| *name == id)
+        .map(|(name, code)| (PathBuf::from(format!("{}/{}.lai", STDLIB, name)), *code))
+}
@@ -1,4 +1,9 @@
 use super::content::Content;
+use crate::parser::statement::{Field, Statement};
+use crate::parser::types::Type;
+use crate::tokenizer::token::Token;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 pub fn is_true(content: &Content) -> bool {
     match content {
@@ -7,10 +12,95 @@ pub fn is_true(content: &Content) -> bool {
         Content::Floating(f) => *f != 0.0,
         Content::Character(c) => *c != '\0',
         Content::String_(s) => s.len() != 0,
+        Content::Range(_) => true,
+        Content::Function(_) => true,
+        Content::Enum(_) => true,
+        Content::Variant(_) => true,
+        Content::Constructor(_) => true,
+        Content::Class(_) => true,
+        Content::Instance(_) => true,
+        Content::Interface(_) => true,
+        Content::Singleton(_) => true,
+        Content::Signal(_) => true,
+        Content::Native(_) => true,
+        Content::Coroutine(_) => true,
+        Content::Module(_) => true,
         Content::Null => false,
     }
 }
 
+// Statements of the program, without the block wrapping them.
+pub fn top_level(statements: &Vec<Statement>) -> Vec<&Statement> {
+    let mut top: Vec<&Statement> = Vec::new();
+
+    for stmt in statements {
+        match stmt {
+            Statement::Block { stmts, level: 0 } => top.extend(stmts.iter().map(|s| &**s)),
+            s => top.push(s),
+        }
+    }
+
+    top
+}
+
+// Find the module file in the first directory that has it.
+pub fn resolve(directories: &Vec<PathBuf>, path: &Vec<Token>) -> Option<PathBuf> {
+    for directory in directories {
+        let mut file: PathBuf = directory.clone();
+
+        for p in path {
+            file.push(&p.lexeme);
+        }
+
+        file.set_extension("lai");
+
+        match file.canonicalize() {
+            Ok(f) if f.is_file() => return Some(f),
+            _ => (),
+        }
+    }
+
+    None
+}
+
+// Expected type when the content doesn't match the annotation.
+pub fn mismatch<'a>(content: &Content, annotation: &'a Option<Type>) -> Option<&'a Type> {
+    match annotation {
+        Some(t) if !is_type(content, t) => Some(t),
+        _ => None,
+    }
+}
+
+fn is_type(content: &Content, t: &Type) -> bool {
+    match (t, content) {
+        (Type::Boolean, Content::Boolean(_)) => true,
+        (Type::Integer, Content::Integer(_)) => true,
+        (Type::Floating, Content::Floating(_)) => true,
+        (Type::Character, Content::Character(_)) => true,
+        (Type::String_, Content::String_(_)) => true,
+        (Type::Void, Content::Null) => true,
+        (Type::Nullable(_), Content::Null) => true,
+        (Type::Nullable(t), c) => is_type(c, t),
+        _ => false,
+    }
+}
+
+// Annotated fields by name.
+pub fn field_types(fields: &Vec<Field>) -> HashMap<String, Type> {
+    let mut types: HashMap<String, Type> = HashMap::new();
+
+    for field in fields {
+        match &field.annotation {
+            Some(t) => {
+                types.insert(field.id.lexeme.clone(), t.clone());
+            }
+            None => (),
+        }
+    }
+
+    types
+}
+
 pub fn concat_strings(s1: &String, s2: &String) -> String {
     let mut s3 = s1.clone();
     s3.push_str(s2.as_str());
@@ -1,8 +1,10 @@
+mod checker;
 mod error;
 mod interpreter;
 mod parser;
 mod tokenizer;
 
+use checker::Checker;
 use error::{clear_errors, code_error, had_error, ExitCode};
 use interpreter::Interpreter;
 use parser::debug::output_tree;
@@ -11,25 +13,93 @@ use parser::Parser;
 use std::env;
 use std::fs::File;
 use std::io::{stdin, stdout, BufReader, Read, Write};
+use std::path::PathBuf;
 use std::process;
+use std::thread;
 use tokenizer::debug::output_tokens;
 use tokenizer::token::Token;
 use tokenizer::Tokenizer;
 
+// Lai calls recurse in Rust, so deep recursion in scripts needs a larger stack than
+// the main thread has.
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() > 2 {
-        println!("Usage: lai [file]");
-        process::exit(ExitCode::USAGE as i32)
-    } else if args.len() == 2 {
-        run_file(&args[1])
-    } else {
-        run_prompt();
+    let child = thread::Builder::new().stack_size(STACK_SIZE).spawn(start);
+
+    match child.map(|c| c.join()) {
+        Ok(Ok(_)) => (),
+        _ => process::exit(ExitCode::SOFTWARE as i32),
+    }
+}
+
+fn start() {
+    let mut args = env::args().skip(1).peekable();
+
+    if args.peek().map(|a| a.as_str()) == Some("check") {
+        args.next();
+
+        match (args.next(), args.next()) {
+            (Some(f), None) => check_file(&f),
+            _ => usage(),
+        }
+    }
+
+    let mut includes: Vec<PathBuf> = Vec::new();
+    let mut filepath: Option<String> = None;
+
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), &filepath) {
+            ("-I", _) => match args.next() {
+                Some(dir) => includes.push(PathBuf::from(dir)),
+                None => usage(),
+            },
+            (_, None) => filepath = Some(arg),
+            _ => usage(),
+        }
+    }
+
+    match filepath {
+        Some(f) => run_file(&f, includes),
+        None => run_prompt(includes),
     }
 }
 
-fn run_file(filepath: &String) {
+fn usage() -> ! {
+    println!("Usage: lai [-I dir]... [file]");
+    println!("       lai check file");
+    process::exit(ExitCode::USAGE as i32)
+}
+
+fn run_file(filepath: &String, includes: Vec<PathBuf>) {
+    let code: String = read_file(filepath);
+    let mut interpreter = Interpreter::new(Some(PathBuf::from(filepath)), includes);
+
+    run(code, &mut interpreter);
+
+    match code_error() {
+        ExitCode::OK => (),
+        c => process::exit(c as i32),
+    }
+}
+
+// Report type errors without running the file.
+fn check_file(filepath: &String) -> ! {
+    let code: String = read_file(filepath);
+    let tokens: Vec<Token> = Tokenizer::new(code).tokenize();
+
+    if !had_error() {
+        let statements: Vec<Statement> = Parser::new(tokens).parse();
+
+        if !had_error() {
+            Checker::new().check(&statements);
+        }
+    }
+
+    process::exit(code_error() as i32)
+}
+
+fn read_file(filepath: &String) -> String {
     let file: File = match File::open(&filepath) {
         Ok(f) => f,
         _ => {
@@ -53,18 +123,11 @@ fn run_file(filepath: &String) {
         }
     }
 
-    let mut interpreter = Interpreter::new();
-
-    run(code, &mut interpreter);
-
-    match code_error() {
-        ExitCode::OK => (),
-        c => process::exit(c as i32),
-    }
+    code
 }
 
-fn run_prompt() {
-    let mut interpreter = Interpreter::new();
+fn run_prompt(includes: Vec<PathBuf>) {
+    let mut interpreter = Interpreter::new(None, includes);
 
     loop {
         print!("> ");
@@ -1,135 +1,1300 @@
 pub mod debug;
 pub mod error;
+pub mod exhaustiveness;
 pub mod expression;
+pub mod pattern;
 pub mod statement;
+pub mod types;
 pub mod utility;
 
-use crate::error::parser_error;
+use crate::error::{parser_error, parser_warning};
 use crate::tokenizer::token::Token;
 use crate::tokenizer::token_type::TokenType;
 use error::*;
+use exhaustiveness::check_exhaustiveness;
 use expression::Expression;
-use statement::Statement;
-use std::mem::discriminant;
+use pattern::{Arm, Pattern};
+use statement::{EnumVariant, Field, Method, Param, Signature, Statement};
+use std::mem::{self, discriminant};
+use std::rc::Rc;
+use types::Type;
 use utility::*;
 
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+
+    level: u8,
+    loops: usize,
+    functions: usize,
+    classes: Vec<bool>, // If each enclosing class has a superclass.
+    method: Option<Token>,
+    static_: bool,   // Inside a static method.
+    coroutine: bool, // Directly inside a coroutine, where "yield" is allowed.
 }
 
-impl Parser {
-    pub fn new(tokens: Vec<Token>) -> Parser {
-        Parser {
-            tokens: tokens,
-            current: 0,
+impl Parser {
+    pub fn new(tokens: Vec<Token>) -> Parser {
+        Parser {
+            tokens: tokens,
+            current: 0,
+
+            level: 0,
+            loops: 0,
+            functions: 0,
+            classes: Vec::new(),
+            method: None,
+            static_: false,
+            coroutine: false,
+        }
+    }
+
+    pub fn parse(&mut self) -> Vec<Statement> {
+        let mut statements: Vec<Statement> = Vec::new();
+
+        while !self.is_eof() {
+            match self.declaration() {
+                Ok(s) => statements.push(s),
+                _ => self.synchronize(),
+            }
+        }
+
+        check_exhaustiveness(&statements);
+
+        statements
+    }
+
+    fn declaration(&mut self) -> Result<Statement, ()> {
+        match self.peek().token_type {
+            TokenType::Var => self.var(),
+            TokenType::Const => self.const_(),
+            TokenType::Func | TokenType::Coro => self.func(),
+            TokenType::Enum => self.enum_(),
+            TokenType::Struct => self.struct_(),
+            TokenType::Class | TokenType::Singleton => self.class(),
+            TokenType::Interface => self.interface(),
+            TokenType::Import => self.import(),
+            TokenType::From => self.import_from(),
+            TokenType::Public => self.public(),
+            _ => self.statement(),
+        }
+    }
+
+    // Public declarations are the ones other files can import.
+    fn public(&mut self) -> Result<Statement, ()> {
+        let line: usize = self.advance().line; // Consume "public" token.
+
+        if self.level != 0 {
+            parser_error(line, PUBLIC_OUTSIDE_TOP_LEVEL.to_string());
+            return Err(());
+        }
+
+        let declaration: Statement = match self.peek().token_type {
+            TokenType::Public | TokenType::Import | TokenType::From => {
+                parser_error(self.peek().line, EXPECT_PUBLIC_DECLARATION.to_string());
+                return Err(());
+            }
+            _ => self.declaration()?,
+        };
+
+        match declared_id(&declaration) {
+            Some(_) => Ok(Statement::Public {
+                declaration: Box::new(declaration),
+            }),
+            None => {
+                parser_error(line, EXPECT_PUBLIC_DECLARATION.to_string());
+                Err(())
+            }
+        }
+    }
+
+    fn import(&mut self) -> Result<Statement, ()> {
+        let keyword: Token = self.advance().clone(); // Consume "import" token.
+        let path: Vec<Token> = self.module_path()?;
+        let mut alias: Option<Token> = None;
+
+        if self.advance_if_is(&TokenType::As) {
+            alias = Some(self.alias()?);
+        }
+
+        if self.advance_if_is(&TokenType::Newline) {
+            Ok(Statement::Import {
+                keyword: keyword,
+                path: path,
+                alias: alias,
+            })
+        } else {
+            parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+            Err(())
+        }
+    }
+
+    fn import_from(&mut self) -> Result<Statement, ()> {
+        let keyword: Token = self.advance().clone(); // Consume "from" token.
+        let path: Vec<Token> = self.module_path()?;
+        let mut names: Vec<(Token, Option<Token>)> = Vec::new();
+
+        if !self.advance_if_is(&TokenType::Import) {
+            parser_error(self.peek().line, EXPECT_IMPORT.to_string());
+            return Err(());
+        }
+
+        loop {
+            if !self.is_token(&IDENTIFIER) {
+                parser_error(self.peek().line, EXPECT_IMPORT_IDENTIFIER.to_string());
+                return Err(());
+            }
+
+            let name: Token = self.advance().clone();
+            let mut alias: Option<Token> = None;
+
+            if self.advance_if_is(&TokenType::As) {
+                alias = Some(self.alias()?);
+            }
+
+            names.push((name, alias));
+
+            if !self.advance_if_is(&TokenType::Comma) {
+                break;
+            }
+        }
+
+        if self.advance_if_is(&TokenType::Newline) {
+            Ok(Statement::FromImport {
+                keyword: keyword,
+                path: path,
+                names: names,
+            })
+        } else {
+            parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+            Err(())
+        }
+    }
+
+    // Modules inside directories are separated by ".".
+    fn module_path(&mut self) -> Result<Vec<Token>, ()> {
+        let mut path: Vec<Token> = Vec::new();
+
+        loop {
+            if !self.is_token(&IDENTIFIER) {
+                parser_error(self.peek().line, EXPECT_MODULE_IDENTIFIER.to_string());
+                return Err(());
+            }
+
+            path.push(self.advance().clone());
+
+            if !self.advance_if_is(&TokenType::Period) {
+                return Ok(path);
+            }
+        }
+    }
+
+    fn alias(&mut self) -> Result<Token, ()> {
+        if self.advance_if_is(&IDENTIFIER) {
+            Ok(self.previous().clone())
+        } else {
+            parser_error(self.peek().line, EXPECT_ALIAS_IDENTIFIER.to_string());
+            Err(())
+        }
+    }
+
+    fn var(&mut self) -> Result<Statement, ()> {
+        self.advance(); // Consume "var" token.
+
+        if !self.is_token(&IDENTIFIER) {
+            parser_error(self.peek().line, EXPECT_VAR_IDENTIFIER.to_string());
+            return Err(());
+        }
+
+        let var: Statement;
+        let identifier: Token = self.advance().clone();
+        let annotation: Option<Type> = self.annotation()?;
+
+        if self.advance_if_is(&TokenType::Equal) {
+            if self.is_token(&TokenType::Await) {
+                if annotation.is_some() {
+                    parser_error(self.peek().line, AWAIT_ANNOTATED.to_string());
+                    return Err(());
+                }
+
+                return self.await_(Some(identifier));
+            }
+
+            var = Statement::VarAssign {
+                identifier: identifier,
+                annotation: annotation,
+                expr: Box::new(self.expression()?),
+            }
+        } else {
+            // Variables without a value start as null.
+            match &annotation {
+                Some(Type::Nullable(_)) | None => (),
+                Some(_) => {
+                    parser_error(self.peek().line, UNINITIALIZED_NOT_NULLABLE.to_string());
+                    return Err(());
+                }
+            }
+
+            var = Statement::Var {
+                identifier: identifier,
+                annotation: annotation,
+            }
+        }
+
+        if self.advance_if_is(&TokenType::Newline) {
+            Ok(var)
+        } else {
+            parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+            Err(())
+        }
+    }
+
+    fn const_(&mut self) -> Result<Statement, ()> {
+        self.advance(); // Consume "const" token.
+
+        if !self.is_token(&IDENTIFIER) {
+            parser_error(self.peek().line, EXPECT_CONST_IDENTIFIER.to_string());
+            return Err(());
+        }
+
+        let identifier: Token = self.advance().clone();
+        let annotation: Option<Type> = self.annotation()?;
+
+        if !self.advance_if_is(&TokenType::Equal) {
+            parser_error(self.peek().line, EXPECT_CONST_VALUE.to_string());
+            return Err(());
+        }
+
+        let expr: Expression = self.expression()?;
+
+        if self.advance_if_is(&TokenType::Newline) {
+            Ok(Statement::Const {
+                identifier: identifier,
+                annotation: annotation,
+                expr: Box::new(expr),
+            })
+        } else {
+            parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+            Err(())
+        }
+    }
+
+    fn func(&mut self) -> Result<Statement, ()> {
+        // Consume "func" or "coro" token.
+        let coroutine: bool = self.advance().token_type == TokenType::Coro;
+
+        if !self.is_token(&IDENTIFIER) {
+            let message: &str = match coroutine {
+                true => EXPECT_CORO_IDENTIFIER,
+                false => EXPECT_FUNC_IDENTIFIER,
+            };
+            parser_error(self.peek().line, message.to_string());
+            return Err(());
+        }
+
+        let id: Token = self.advance().clone();
+        let params: Vec<Param> = self.params(true)?;
+        let output: Option<Type> = self.output()?;
+
+        let method: Option<Token> = self.method.take();
+        let body: Result<Statement, ()> = self.function_body(coroutine);
+        self.method = method;
+
+        match coroutine {
+            true => Ok(Statement::Coro {
+                id: id,
+                params: params,
+                output: output,
+                body: Rc::new(body?),
+            }),
+            false => Ok(Statement::Func {
+                id: id,
+                params: params,
+                output: output,
+                body: Rc::new(body?),
+            }),
+        }
+    }
+
+    fn function_body(&mut self, coroutine: bool) -> Result<Statement, ()> {
+        // Loops outside the function can't be controlled from inside it.
+        let loops: usize = self.loops;
+        let coroutine: bool = mem::replace(&mut self.coroutine, coroutine);
+        self.loops = 0;
+        self.functions += 1;
+        let body: Result<Statement, ()> = self.scope();
+        self.functions -= 1;
+        self.coroutine = coroutine;
+        self.loops = loops;
+
+        body
+    }
+
+    fn class(&mut self) -> Result<Statement, ()> {
+        // Consume "class" or "singleton" token.
+        let keyword: Token = self.advance().clone();
+        let singleton: bool = keyword.token_type == TokenType::Singleton;
+
+        // Singletons are created once, so they can't be declared again by a function.
+        if singleton && self.level != 0 {
+            parser_error(keyword.line, SINGLETON_OUTSIDE_TOP_LEVEL.to_string());
+            return Err(());
+        }
+
+        if !self.is_token(&IDENTIFIER) {
+            let message: &str = match singleton {
+                true => EXPECT_SINGLETON_IDENTIFIER,
+                false => EXPECT_CLASS_IDENTIFIER,
+            };
+            parser_error(self.peek().line, message.to_string());
+            return Err(());
+        }
+
+        let id: Token = self.advance().clone();
+        let mut superclass: Option<Token> = None;
+
+        if self.advance_if_is(&TokenType::Extends) {
+            if !self.is_token(&IDENTIFIER) {
+                parser_error(self.peek().line, EXPECT_SUPERCLASS_IDENTIFIER.to_string());
+                return Err(());
+            }
+
+            let s: Token = self.advance().clone();
+
+            if s.lexeme == id.lexeme {
+                parser_error(s.line, class_inherits_itself(&id.lexeme));
+                return Err(());
+            }
+
+            superclass = Some(s);
+        }
+
+        let mut interfaces: Vec<Token> = Vec::new();
+
+        if self.advance_if_is(&TokenType::Implements) {
+            loop {
+                if !self.is_token(&IDENTIFIER) {
+                    parser_error(self.peek().line, EXPECT_INTERFACE_IDENTIFIER.to_string());
+                    return Err(());
+                }
+
+                let interface: Token = self.advance().clone();
+
+                if interfaces.iter().any(|i| i.lexeme == interface.lexeme) {
+                    parser_error(interface.line, interface_duplicated(&interface.lexeme));
+                    return Err(());
+                }
+
+                interfaces.push(interface);
+
+                if !self.advance_if_is(&TokenType::Comma) {
+                    break;
+                }
+            }
+        }
+
+        let level: u8 = self.open_scope()?;
+        let mut fields: Vec<Field> = Vec::new();
+        let mut methods: Vec<Method> = Vec::new();
+        let mut signals: Vec<Signature> = Vec::new();
+
+        let enclosing: u8 = self.level;
+        let static_: bool = self.static_;
+        self.level = level;
+        self.static_ = false;
+        self.classes.push(superclass.is_some());
+        let members: Result<(), ()> =
+            self.class_members(level, &mut fields, &mut methods, &mut signals);
+        self.classes.pop();
+        self.static_ = static_;
+        self.level = enclosing;
+
+        members?;
+
+        // Nobody can give arguments to the lazy instantiation of a singleton.
+        for method in &methods {
+            if singleton
+                && method.id.token_type == TokenType::Constructor
+                && method.params.len() != 0
+            {
+                parser_error(method.id.line, SINGLETON_CONSTRUCTOR_PARAMETERS.to_string());
+                return Err(());
+            }
+        }
+
+        Ok(Statement::Class {
+            singleton: singleton,
+            id: id,
+            superclass: superclass,
+            interfaces: interfaces,
+            fields: fields,
+            methods: methods,
+            signals: signals,
+        })
+    }
+
+    fn interface(&mut self) -> Result<Statement, ()> {
+        self.advance(); // Consume "interface" token.
+
+        if !self.is_token(&IDENTIFIER) {
+            parser_error(self.peek().line, EXPECT_INTERFACE_IDENTIFIER.to_string());
+            return Err(());
+        }
+
+        let id: Token = self.advance().clone();
+        let level: u8 = self.open_scope()?;
+        let mut signatures: Vec<Signature> = Vec::new();
+
+        while self.is_line_at(level) {
+            self.advance(); // Consume "tab" token.
+
+            if !self.advance_if_is(&TokenType::Func) {
+                parser_error(self.peek().line, EXPECT_SIGNATURE.to_string());
+                return Err(());
+            }
+
+            if !self.is_token(&IDENTIFIER) {
+                parser_error(self.peek().line, EXPECT_FUNC_IDENTIFIER.to_string());
+                return Err(());
+            }
+
+            let signature: Signature = Signature {
+                id: self.advance().clone(),
+                params: self.params(true)?,
+                output: self.output()?,
+            };
+
+            if !self.advance_if_is(&TokenType::Newline) {
+                parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+                return Err(());
+            }
+
+            if signatures
+                .iter()
+                .any(|s| s.id.lexeme == signature.id.lexeme)
+            {
+                parser_error(signature.id.line, method_duplicated(&signature.id.lexeme));
+                return Err(());
+            }
+
+            signatures.push(signature);
+        }
+
+        Ok(Statement::Interface {
+            id: id,
+            signatures: signatures,
+        })
+    }
+
+    fn class_members(
+        &mut self,
+        level: u8,
+        fields: &mut Vec<Field>,
+        methods: &mut Vec<Method>,
+        signals: &mut Vec<Signature>,
+    ) -> Result<(), ()> {
+        while self.is_line_at(level) {
+            self.advance(); // Consume "tab" token.
+
+            let mut public: bool = false;
+            let mut static_: bool = false;
+
+            while self.advance_if_is_any_of(&MODIFIERS) {
+                let modifier: Token = self.previous().clone();
+                let used: &mut bool = match modifier.token_type {
+                    TokenType::Public => &mut public,
+                    _ => &mut static_,
+                };
+
+                if *used {
+                    parser_error(modifier.line, modifier_duplicated(&modifier.lexeme));
+                    return Err(());
+                }
+
+                *used = true;
+            }
+
+            match self.peek().token_type {
+                TokenType::Var => {
+                    self.advance(); // Consume "var" token.
+                    let mut field: Field = self.field()?;
+                    field.public = public;
+                    field.static_ = static_;
+
+                    if static_ && (field.getter.is_some() || field.setter.is_some()) {
+                        parser_error(field.id.line, STATIC_FIELD_ACCESSORS.to_string());
+                        return Err(());
+                    }
+
+                    if fields.iter().any(|f| f.id.lexeme == field.id.lexeme) {
+                        parser_error(field.id.line, field_duplicated(&field.id.lexeme));
+                        return Err(());
+                    }
+
+                    fields.push(field);
+                }
+                TokenType::Constructor | TokenType::Destructor | TokenType::Signal
+                    if public || static_ =>
+                {
+                    parser_error(self.peek().line, EXPECT_MODIFIED_MEMBER.to_string());
+                    return Err(());
+                }
+                TokenType::Func
+                | TokenType::Coro
+                | TokenType::Constructor
+                | TokenType::Destructor => {
+                    let keyword: Token = self.advance().clone();
+                    let coroutine: bool = keyword.token_type == TokenType::Coro;
+
+                    let id: Token = match keyword.token_type {
+                        TokenType::Func | TokenType::Coro if self.is_token(&IDENTIFIER) => {
+                            self.advance().clone()
+                        }
+                        TokenType::Func => {
+                            parser_error(self.peek().line, EXPECT_FUNC_IDENTIFIER.to_string());
+                            return Err(());
+                        }
+                        TokenType::Coro => {
+                            parser_error(self.peek().line, EXPECT_CORO_IDENTIFIER.to_string());
+                            return Err(());
+                        }
+                        _ => keyword,
+                    };
+
+                    let mut method: Method = self.method(id, static_, coroutine)?;
+                    method.public = public;
+
+                    if methods.iter().any(|m| m.id.lexeme == method.id.lexeme) {
+                        parser_error(method.id.line, method_duplicated(&method.id.lexeme));
+                        return Err(());
+                    }
+
+                    methods.push(method);
+                }
+                TokenType::Signal => {
+                    let signal: Signature = self.signal()?;
+
+                    if signals.iter().any(|s| s.id.lexeme == signal.id.lexeme) {
+                        parser_error(signal.id.line, signal_duplicated(&signal.id.lexeme));
+                        return Err(());
+                    }
+
+                    signals.push(signal);
+                }
+                _ if public || static_ => {
+                    parser_error(self.peek().line, EXPECT_MODIFIED_MEMBER.to_string());
+                    return Err(());
+                }
+                _ => {
+                    parser_error(self.peek().line, EXPECT_CLASS_MEMBER.to_string());
+                    return Err(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn method(&mut self, id: Token, static_: bool, coroutine: bool) -> Result<Method, ()> {
+        // Destructors are never called with arguments.
+        let params: Vec<Param> = match id.token_type {
+            TokenType::Destructor => Vec::new(),
+            _ => self.params(true)?,
+        };
+        let output: Option<Type> = match id.token_type {
+            TokenType::Constructor | TokenType::Destructor => None,
+            _ => self.output()?,
+        };
+
+        let method: Option<Token> = self.method.replace(id.clone());
+        let enclosing: bool = mem::replace(&mut self.static_, static_);
+        let body: Result<Statement, ()> = self.function_body(coroutine);
+        self.static_ = enclosing;
+        self.method = method;
+
+        Ok(Method {
+            public: false,
+            static_: static_,
+            coroutine: coroutine,
+            id: id,
+            params: params,
+            output: output,
+            body: Rc::new(body?),
+        })
+    }
+
+    fn signal(&mut self) -> Result<Signature, ()> {
+        self.advance(); // Consume "signal" token.
+
+        if !self.is_token(&IDENTIFIER) {
+            parser_error(self.peek().line, EXPECT_SIGNAL_IDENTIFIER.to_string());
+            return Err(());
+        }
+
+        let signal: Signature = Signature {
+            id: self.advance().clone(),
+            params: self.params(true)?,
+            output: None,
+        };
+
+        if !self.advance_if_is(&TokenType::Newline) {
+            parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+            return Err(());
+        }
+
+        Ok(signal)
+    }
+
+    fn enum_(&mut self) -> Result<Statement, ()> {
+        self.advance(); // Consume "enum" token.
+
+        if !self.is_token(&IDENTIFIER) {
+            parser_error(self.peek().line, EXPECT_ENUM_IDENTIFIER.to_string());
+            return Err(());
+        }
+
+        let id: Token = self.advance().clone();
+        let level: u8 = self.open_scope()?;
+        let mut variants: Vec<EnumVariant> = Vec::new();
+
+        while self.is_line_at(level) {
+            self.advance(); // Consume "tab" token.
+
+            if !self.is_token(&IDENTIFIER) {
+                parser_error(self.peek().line, EXPECT_VARIANT_IDENTIFIER.to_string());
+                return Err(());
+            }
+
+            let variant: Token = self.advance().clone();
+            let mut fields: Vec<Token> = Vec::new();
+
+            if variants.iter().any(|v| v.id.lexeme == variant.lexeme) {
+                parser_error(variant.line, variant_duplicated(&variant.lexeme));
+                return Err(());
+            }
+
+            if self.is_token(&TokenType::ParenthesisOpen) {
+                fields = self.params(false)?.into_iter().map(|p| p.id).collect();
+            }
+
+            if !self.advance_if_is(&TokenType::Newline) {
+                parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+                return Err(());
+            }
+
+            variants.push(EnumVariant {
+                id: variant,
+                fields: fields,
+            });
+        }
+
+        Ok(Statement::Enum {
+            id: id,
+            variants: variants,
+        })
+    }
+
+    fn struct_(&mut self) -> Result<Statement, ()> {
+        self.advance(); // Consume "struct" token.
+
+        if !self.is_token(&IDENTIFIER) {
+            parser_error(self.peek().line, EXPECT_STRUCT_IDENTIFIER.to_string());
+            return Err(());
+        }
+
+        let id: Token = self.advance().clone();
+        let level: u8 = self.open_scope()?;
+        let mut fields: Vec<Field> = Vec::new();
+
+        // Accessors can use "self" as in classes.
+        let enclosing: u8 = self.level;
+        self.level = level;
+        self.classes.push(false);
+        let members: Result<(), ()> = self.struct_fields(level, &mut fields);
+        self.classes.pop();
+        self.level = enclosing;
+
+        members?;
+
+        Ok(Statement::Struct {
+            id: id,
+            fields: fields,
+        })
+    }
+
+    fn struct_fields(&mut self, level: u8, fields: &mut Vec<Field>) -> Result<(), ()> {
+        while self.is_line_at(level) {
+            self.advance(); // Consume "tab" token.
+
+            if !self.advance_if_is(&TokenType::Var) {
+                parser_error(self.peek().line, EXPECT_STRUCT_FIELD.to_string());
+                return Err(());
+            }
+
+            let field: Field = self.field()?;
+
+            if fields.iter().any(|f| f.id.lexeme == field.id.lexeme) {
+                parser_error(field.id.line, field_duplicated(&field.id.lexeme));
+                return Err(());
+            }
+
+            fields.push(field);
+        }
+
+        Ok(())
+    }
+
+    fn field(&mut self) -> Result<Field, ()> {
+        if !self.is_token(&IDENTIFIER) {
+            parser_error(self.peek().line, EXPECT_FIELD_IDENTIFIER.to_string());
+            return Err(());
+        }
+
+        let id: Token = self.advance().clone();
+        let annotation: Option<Type> = self.annotation()?;
+        let mut default: Option<Rc<Expression>> = None;
+
+        if self.advance_if_is(&TokenType::Equal) {
+            default = Some(Rc::new(self.expression()?));
+        } else {
+            // Fields without a default start as null, like variables.
+            match &annotation {
+                Some(Type::Nullable(_)) | None => (),
+                Some(_) => {
+                    parser_error(self.peek().line, UNINITIALIZED_NOT_NULLABLE.to_string());
+                    return Err(());
+                }
+            }
+        }
+
+        let mut field: Field = Field {
+            public: false,
+            static_: false,
+            id: id,
+            annotation: annotation,
+            default: default,
+            getter: None,
+            setter: None,
+        };
+
+        if self.is_token(&TokenType::Colon) {
+            let level: u8 = self.open_scope()?;
+
+            let enclosing: u8 = self.level;
+            self.level = level;
+            let accessors: Result<(), ()> = self.accessors(level, &mut field);
+            self.level = enclosing;
+
+            accessors?;
+        } else if !self.advance_if_is(&TokenType::Newline) {
+            parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+            return Err(());
+        }
+
+        Ok(field)
+    }
+
+    fn accessors(&mut self, level: u8, field: &mut Field) -> Result<(), ()> {
+        while self.is_line_at(level) {
+            self.advance(); // Consume "tab" token.
+
+            let accessor: Token = self.advance().clone();
+
+            match accessor.token_type {
+                TokenType::Get if field.getter.is_none() => {
+                    field.getter = Some(Rc::new(self.accessor_body()?));
+                }
+                TokenType::Set if field.setter.is_none() => {
+                    let params: Vec<Param> = self.params(false)?;
+
+                    if params.len() != 1 {
+                        parser_error(accessor.line, EXPECT_SETTER_PARAMETER.to_string());
+                        return Err(());
+                    }
+
+                    let body: Statement = self.accessor_body()?;
+                    field.setter = Some((params[0].id.clone(), Rc::new(body)));
+                }
+                TokenType::Get | TokenType::Set => {
+                    parser_error(accessor.line, accessor_duplicated(&accessor.lexeme));
+                    return Err(());
+                }
+                _ => {
+                    parser_error(accessor.line, EXPECT_ACCESSOR.to_string());
+                    return Err(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn accessor_body(&mut self) -> Result<Statement, ()> {
+        let method: Option<Token> = self.method.take();
+        let body: Result<Statement, ()> = self.function_body(false);
+        self.method = method;
+
+        body
+    }
+
+    // Type after ":", as in "var x: int".
+    fn annotation(&mut self) -> Result<Option<Type>, ()> {
+        // A ":" ending the line opens the accessors of a field instead.
+        if !self.is_token(&TokenType::Colon) || self.peek_next().token_type == TokenType::Newline {
+            return Ok(None);
+        }
+
+        self.advance(); // Consume ":" token.
+
+        match self.type_()? {
+            Type::Void => {
+                parser_error(self.previous().line, VOID_OUTSIDE_RETURN.to_string());
+                Err(())
+            }
+            t => Ok(Some(t)),
+        }
+    }
+
+    // Return type after "->".
+    fn output(&mut self) -> Result<Option<Type>, ()> {
+        if self.advance_if_is(&TokenType::ForwardArrow) {
+            Ok(Some(self.type_()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn type_(&mut self) -> Result<Type, ()> {
+        let type_: Type = match Type::from(&self.peek().token_type) {
+            Some(t) => {
+                self.advance(); // Consume type token.
+                t
+            }
+            None => {
+                parser_error(self.peek().line, EXPECT_TYPE.to_string());
+                return Err(());
+            }
+        };
+
+        if !self.advance_if_is(&TokenType::QuestionMark) {
+            return Ok(type_);
+        }
+
+        match type_ {
+            Type::Void => {
+                parser_error(self.previous().line, NULLABLE_VOID.to_string());
+                Err(())
+            }
+            t => Ok(Type::Nullable(Box::new(t))),
+        }
+    }
+
+    // Only parameters of functions can have types.
+    fn params(&mut self, typed: bool) -> Result<Vec<Param>, ()> {
+        let mut params: Vec<Param> = Vec::new();
+
+        if !self.advance_if_is(&TokenType::ParenthesisOpen) {
+            parser_error(
+                self.peek().line,
+                EXPECT_OPEN_PARENTHESIS_PARAMETERS.to_string(),
+            );
+            return Err(());
+        }
+
+        if !self.is_token(&TokenType::ParenthesisClose) {
+            loop {
+                if !self.is_token(&IDENTIFIER) {
+                    parser_error(self.peek().line, EXPECT_PARAMETER_IDENTIFIER.to_string());
+                    return Err(());
+                }
+
+                let id: Token = self.advance().clone();
+                let annotation: Option<Type> = match typed {
+                    true => self.annotation()?,
+                    false => None,
+                };
+
+                params.push(Param {
+                    id: id,
+                    annotation: annotation,
+                });
+
+                if !self.advance_if_is(&TokenType::Comma) {
+                    break;
+                }
+            }
+        }
+
+        if !self.advance_if_is(&TokenType::ParenthesisClose) {
+            parser_error(
+                self.peek().line,
+                EXPECT_CLOSE_PARENTHESIS_PARAMETERS.to_string(),
+            );
+            return Err(());
+        }
+
+        Ok(params)
+    }
+
+    fn statement(&mut self) -> Result<Statement, ()> {
+        match self.peek().token_type {
+            TokenType::If => self.if_(),
+            TokenType::While => self.while_(),
+            TokenType::For => self.for_(),
+            TokenType::Loop => self.loop_(),
+            TokenType::Match => self.match_(),
+            TokenType::Break => self.break_(),
+            TokenType::Continue => self.continue_(),
+            TokenType::Return => self.return_(),
+            TokenType::Yield => self.yield_(),
+            TokenType::Await => self.await_(None),
+            TokenType::Print => self.print(),
+            TokenType::Emit => self.emit(),
+            TokenType::Indent(level) => self.block(level),
+            TokenType::Newline => self.empty_line(),
+            _ => self.expr(),
+        }
+    }
+
+    fn if_(&mut self) -> Result<Statement, ()> {
+        self.advance(); // Consume "if" token.
+
+        let condition: Expression = self.expression()?;
+        let if_statement: Statement = self.scope()?;
+
+        if !self.is_else() {
+            return Ok(Statement::If {
+                condition: Box::new(condition),
+                statement: Rc::new(if_statement),
+            });
+        }
+
+        self.advance(); // Consume "tab" token.
+        self.advance(); // Consume "else" token.
+
+        Ok(Statement::IfElse {
+            condition: Box::new(condition),
+            if_statement: Rc::new(if_statement),
+            else_statement: Rc::new(self.scope()?),
+        })
+    }
+
+    fn while_(&mut self) -> Result<Statement, ()> {
+        self.advance(); // Consume "while" token.
+
+        let condition: Expression = self.expression()?;
+
+        self.loops += 1;
+        let statement: Result<Statement, ()> = self.scope();
+        self.loops -= 1;
+
+        Ok(Statement::While {
+            condition: Box::new(condition),
+            statement: Rc::new(statement?),
+        })
+    }
+
+    fn for_(&mut self) -> Result<Statement, ()> {
+        self.advance(); // Consume "for" token.
+
+        if !self.is_token(&IDENTIFIER) {
+            parser_error(self.peek().line, EXPECT_FOR_IDENTIFIER.to_string());
+            return Err(());
+        }
+
+        let id: Token = self.advance().clone();
+
+        if !self.advance_if_is(&TokenType::In) {
+            parser_error(self.peek().line, EXPECT_IN.to_string());
+            return Err(());
+        }
+
+        let iterable: Expression = self.expression()?;
+
+        self.loops += 1;
+        let statement: Result<Statement, ()> = self.scope();
+        self.loops -= 1;
+
+        Ok(Statement::For {
+            id: id,
+            iterable: Box::new(iterable),
+            statement: Rc::new(statement?),
+        })
+    }
+
+    fn loop_(&mut self) -> Result<Statement, ()> {
+        let line: usize = self.advance().line; // Consume "loop" token.
+
+        self.loops += 1;
+        let statement: Result<Statement, ()> = self.scope();
+        self.loops -= 1;
+
+        let statement: Statement = statement?;
+
+        if !is_breakable(&statement) {
+            parser_warning(line, LOOP_WITHOUT_BREAK.to_string());
+        }
+
+        Ok(Statement::Loop {
+            statement: Rc::new(statement),
+        })
+    }
+
+    fn match_(&mut self) -> Result<Statement, ()> {
+        let token: Token = self.advance().clone();
+        let expr: Expression = self.expression()?;
+        let level: u8 = self.open_scope()?;
+
+        let enclosing: u8 = self.level;
+        self.level = level;
+        let arms: Result<Vec<Arm>, ()> = self.arms(level);
+        self.level = enclosing;
+
+        Ok(Statement::Match {
+            token: token,
+            expr: Box::new(expr),
+            arms: arms?,
+        })
+    }
+
+    fn arms(&mut self, level: u8) -> Result<Vec<Arm>, ()> {
+        let mut arms: Vec<Arm> = Vec::new();
+
+        while self.is_line_at(level) {
+            self.advance(); // Consume "tab" token.
+
+            let pattern: Pattern = self.pattern()?;
+            let mut guard: Option<Box<Expression>> = None;
+
+            if self.advance_if_is(&TokenType::When) {
+                guard = Some(Box::new(self.expression()?));
+            }
+
+            arms.push(Arm {
+                pattern: pattern,
+                guard: guard,
+                statement: Rc::new(self.scope()?),
+            });
+        }
+
+        Ok(arms)
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, ()> {
+        if self.advance_if_is(&TokenType::Underscore) {
+            return Ok(Pattern::Wildcard);
+        }
+
+        if self.is_token(&IDENTIFIER) && self.peek_next().token_type == TokenType::Period {
+            let path: Expression = self.pattern_path()?;
+
+            if !self.advance_if_is(&TokenType::ParenthesisOpen) {
+                return Ok(Pattern::Value {
+                    expr: Box::new(path),
+                });
+            }
+
+            let paren: Token = self.previous().clone();
+            let mut fields: Vec<Pattern> = Vec::new();
+
+            if !self.is_token(&TokenType::ParenthesisClose) {
+                loop {
+                    fields.push(self.pattern()?);
+
+                    if !self.advance_if_is(&TokenType::Comma) {
+                        break;
+                    }
+                }
+            }
+
+            if !self.advance_if_is(&TokenType::ParenthesisClose) {
+                parser_error(
+                    self.peek().line,
+                    EXPECT_CLOSE_PARENTHESIS_PATTERNS.to_string(),
+                );
+                return Err(());
+            }
+
+            return Ok(Pattern::Variant {
+                path: Box::new(path),
+                paren: paren,
+                fields: fields,
+            });
+        }
+
+        if self.advance_if_is(&IDENTIFIER) {
+            return Ok(Pattern::Binding {
+                id: self.previous().clone(),
+            });
+        }
+
+        let start: Expression = self.pattern_literal()?;
+
+        if self.advance_if_is_any_of(&RANGES) {
+            let op: Token = self.previous().clone();
+            let end: Expression = self.pattern_literal()?;
+
+            return Ok(Pattern::Range {
+                start: Box::new(start),
+                op: op,
+                end: Box::new(end),
+            });
         }
+
+        Ok(Pattern::Value {
+            expr: Box::new(start),
+        })
     }
 
-    pub fn parse(&mut self) -> Vec<Statement> {
-        let mut statements: Vec<Statement> = Vec::new();
+    // Parse a dotted name like "Color.Red".
+    fn pattern_path(&mut self) -> Result<Expression, ()> {
+        let mut expr: Expression = Expression::Variable {
+            id: self.advance().clone(),
+        };
 
-        while !self.is_eof() {
-            match self.declaration() {
-                Ok(s) => statements.push(s),
-                _ => self.synchronize(),
-            }
+        while self.advance_if_is(&TokenType::Period) {
+            expr = Expression::Get {
+                object: Box::new(expr),
+                name: self.property()?,
+            };
         }
 
-        statements
+        Ok(expr)
     }
 
-    fn declaration(&mut self) -> Result<Statement, ()> {
-        match self.peek().token_type {
-            TokenType::Var => self.var(),
-            _ => self.statement(),
+    fn pattern_literal(&mut self) -> Result<Expression, ()> {
+        if self.advance_if_is(&TokenType::Minus) {
+            let op: Token = self.previous().clone();
+
+            return Ok(Expression::Unary {
+                op: op,
+                right: Box::new(self.pattern_literal()?),
+            });
+        }
+
+        if self.advance_if_is_any_of(&LITERALS) {
+            Ok(Expression::Literal {
+                token: self.previous().clone(),
+            })
+        } else {
+            parser_error(self.peek().line, EXPECT_PATTERN.to_string());
+            Err(())
         }
     }
 
-    fn var(&mut self) -> Result<Statement, ()> {
-        self.advance(); // Consume "var" token.
+    fn break_(&mut self) -> Result<Statement, ()> {
+        let line: usize = self.advance().line; // Consume "break" token.
 
-        if !self.is_token(&IDENTIFIER) {
-            parser_error(self.peek().line, EXPECT_VAR_IDENTIFIER.to_string());
+        if self.loops == 0 {
+            parser_error(line, BREAK_OUTSIDE_LOOP.to_string());
             return Err(());
         }
 
-        let var: Statement;
-        let identifier: Token = self.advance().clone();
-
-        if self.advance_if_is(&TokenType::Equal) {
-            var = Statement::VarAssign {
-                identifier: identifier,
-                expr: Box::new(self.expression()?),
-            }
+        if self.advance_if_is(&TokenType::Newline) {
+            Ok(Statement::Break)
         } else {
-            var = Statement::Var {
-                identifier: identifier,
-            }
+            parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+            Err(())
+        }
+    }
+
+    fn continue_(&mut self) -> Result<Statement, ()> {
+        let line: usize = self.advance().line; // Consume "continue" token.
+
+        if self.loops == 0 {
+            parser_error(line, CONTINUE_OUTSIDE_LOOP.to_string());
+            return Err(());
         }
 
         if self.advance_if_is(&TokenType::Newline) {
-            Ok(var)
+            Ok(Statement::Continue)
         } else {
             parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
             Err(())
         }
     }
 
-    fn statement(&mut self) -> Result<Statement, ()> {
-        match self.peek().token_type {
-            TokenType::If => self.if_(),
-            TokenType::Print => self.print(),
-            TokenType::Indent(level) => self.block(level),
-            TokenType::Newline => self.empty_line(),
-            _ => self.expr(),
+    fn return_(&mut self) -> Result<Statement, ()> {
+        let line: usize = self.advance().line; // Consume "return" token.
+
+        if self.functions == 0 {
+            parser_error(line, RETURN_OUTSIDE_FUNCTION.to_string());
+            return Err(());
         }
-    }
 
-    fn if_(&mut self) -> Result<Statement, ()> {
-        self.advance(); // Consume "if" token.
+        let mut expr: Option<Box<Expression>> = None;
 
-        let condition: Expression = self.expression()?;
+        if !self.is_token(&TokenType::Newline) {
+            let method: Option<TokenType> = self.method.as_ref().map(|m| m.token_type.clone());
 
-        if !self.advance_if_is(&TokenType::Colon) {
-            parser_error(self.peek().line, EXPECT_COLON.to_string());
-            return Err(());
+            match method {
+                Some(TokenType::Constructor) => {
+                    parser_error(line, RETURN_VALUE_IN_CONSTRUCTOR.to_string());
+                    return Err(());
+                }
+                Some(TokenType::Destructor) => {
+                    parser_error(line, RETURN_VALUE_IN_DESTRUCTOR.to_string());
+                    return Err(());
+                }
+                _ => (),
+            }
+
+            expr = Some(Box::new(self.expression()?));
         }
 
-        if !self.advance_if_is(&TokenType::Newline) {
+        if self.advance_if_is(&TokenType::Newline) {
+            Ok(Statement::Return { expr: expr })
+        } else {
             parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
-            return Err(());
+            Err(())
         }
+    }
 
-        let if_statement = self.statement()?;
+    fn yield_(&mut self) -> Result<Statement, ()> {
+        let keyword: Token = self.advance().clone(); // Consume "yield" token.
 
-        if !self.advance_if_is(&INDENT) {
-            parser_error(self.peek().line, EXPECT_INDENT.to_string());
+        if !self.coroutine {
+            parser_error(keyword.line, YIELD_OUTSIDE_COROUTINE.to_string());
             return Err(());
         }
 
-        if !self.advance_if_is(&TokenType::Else) {
-            return Ok(Statement::If {
-                condition: Box::new(condition),
-                statement: Box::new(if_statement),
-            });
+        let expr: Option<Box<Expression>> = match self.is_token(&TokenType::Newline) {
+            true => None,
+            false => Some(Box::new(self.expression()?)),
+        };
+
+        if self.advance_if_is(&TokenType::Newline) {
+            Ok(Statement::Yield {
+                keyword: keyword,
+                expr: expr,
+            })
+        } else {
+            parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+            Err(())
         }
+    }
 
-        if !self.advance_if_is(&TokenType::Colon) {
-            parser_error(self.peek().line, EXPECT_COLON.to_string());
+    // Awaiting can also declare a variable receiving the awaited value.
+    fn await_(&mut self, id: Option<Token>) -> Result<Statement, ()> {
+        let keyword: Token = self.advance().clone(); // Consume "await" token.
+
+        if !self.coroutine {
+            parser_error(keyword.line, AWAIT_OUTSIDE_COROUTINE.to_string());
             return Err(());
         }
 
-        if !self.advance_if_is(&TokenType::Newline) {
+        let expr: Expression = self.expression()?;
+
+        if self.advance_if_is(&TokenType::Newline) {
+            Ok(Statement::Await {
+                keyword: keyword,
+                expr: Box::new(expr),
+                id: id,
+            })
+        } else {
             parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
-            return Err(());
+            Err(())
         }
-
-        Ok(Statement::IfElse {
-            condition: Box::new(condition),
-            if_statement: Box::new(if_statement),
-            else_statement: Box::new(self.statement()?),
-        })
     }
 
     fn print(&mut self) -> Result<Statement, ()> {
@@ -147,10 +1312,58 @@ impl Parser {
         }
     }
 
+    fn emit(&mut self) -> Result<Statement, ()> {
+        let keyword: Token = self.advance().clone(); // Consume "emit" token.
+
+        let (callee, args) = match self.call()? {
+            Expression::Call {
+                callee,
+                paren: _,
+                args,
+                named,
+            } if named.is_empty() => (callee, args),
+            _ => {
+                parser_error(keyword.line, EXPECT_SIGNAL_CALL.to_string());
+                return Err(());
+            }
+        };
+
+        // Inside of a method, a bare name refers to a signal of the instance.
+        let signal: Expression = match *callee {
+            Expression::Variable { id } if !self.classes.is_empty() && !self.static_ => {
+                let self_: Token = Token {
+                    token_type: TokenType::Self_,
+                    lexeme: "self".to_string(),
+                    line: keyword.line,
+                };
+
+                Expression::Get {
+                    object: Box::new(Expression::Self_ { keyword: self_ }),
+                    name: id,
+                }
+            }
+            callee => callee,
+        };
+
+        if self.advance_if_is(&TokenType::Newline) {
+            Ok(Statement::Emit {
+                keyword: keyword,
+                signal: Box::new(signal),
+                args: args,
+            })
+        } else {
+            parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+            Err(())
+        }
+    }
+
     fn block(&mut self, level: u8) -> Result<Statement, ()> {
         self.advance(); // Consume "tab" token.
 
-        let mut statements: Vec<Statement> = Vec::new();
+        let enclosing: u8 = self.level;
+        let mut statements: Vec<Rc<Statement>> = Vec::new();
+
+        self.level = level;
 
         while !self.is_eof() {
             match self.peek().token_type {
@@ -162,21 +1375,54 @@ impl Parser {
                         break; // Leave scope.
                     }
                 }
+                TokenType::Newline => {
+                    self.advance(); // Consume empty line.
+                    continue;
+                }
                 _ => (),
             }
 
             match self.declaration() {
-                Ok(s) => statements.push(s),
+                Ok(s) => statements.push(Rc::new(s)),
                 _ => self.synchronize(),
             }
         }
 
+        self.level = enclosing;
+
         Ok(Statement::Block {
             stmts: statements,
             level: level,
         })
     }
 
+    // Parse the indented block that follows a ":".
+    fn scope(&mut self) -> Result<Statement, ()> {
+        let level: u8 = self.open_scope()?;
+        self.block(level)
+    }
+
+    // Consume the ":" and newline that start a scope, returning its indentation.
+    fn open_scope(&mut self) -> Result<u8, ()> {
+        if !self.advance_if_is(&TokenType::Colon) {
+            parser_error(self.peek().line, EXPECT_COLON.to_string());
+            return Err(());
+        }
+
+        if !self.advance_if_is(&TokenType::Newline) {
+            parser_error(self.peek().line, EXPECT_NEWLINE.to_string());
+            return Err(());
+        }
+
+        match self.peek().token_type {
+            TokenType::Indent(level) if level > self.level => Ok(level),
+            _ => {
+                parser_error(self.peek().line, EXPECT_INDENT.to_string());
+                Err(())
+            }
+        }
+    }
+
     fn empty_line(&mut self) -> Result<Statement, ()> {
         // Force synchronization to an useful line (or same line if there is still code in it).
         Err(())
@@ -200,7 +1446,7 @@ impl Parser {
     }
 
     fn assignment(&mut self) -> Result<Expression, ()> {
-        let mut expr: Expression = self.or()?;
+        let mut expr: Expression = self.range()?;
 
         if self.advance_if_is_any_of(&ASSIGNMENTS) {
             let op: Token = self.previous().clone();
@@ -212,6 +1458,12 @@ impl Parser {
                     op: op,
                     right: Box::new(right),
                 },
+                Expression::Get { object, name } => Expression::Set {
+                    object: object,
+                    name: name,
+                    op: op,
+                    right: Box::new(right),
+                },
                 _ => {
                     parser_error(op.line, invalid_var_on_assignment(&op.lexeme));
                     return Err(());
@@ -222,6 +1474,47 @@ impl Parser {
         Ok(expr)
     }
 
+    fn range(&mut self) -> Result<Expression, ()> {
+        let mut expr: Expression = self.coalesce()?;
+
+        if self.advance_if_is_any_of(&RANGES) {
+            let op: Token = self.previous().clone();
+            let end: Expression = self.coalesce()?;
+            let mut step: Option<Box<Expression>> = None;
+
+            if self.advance_if_is(&TokenType::PeriodPeriod) {
+                step = Some(Box::new(self.coalesce()?));
+            }
+
+            expr = Expression::Range {
+                start: Box::new(expr),
+                op: op,
+                end: Box::new(end),
+                step: step,
+            };
+        }
+
+        Ok(expr)
+    }
+
+    // Right associative, "a ?? b ?? c" is "a ?? (b ?? c)".
+    fn coalesce(&mut self) -> Result<Expression, ()> {
+        let mut expr: Expression = self.or()?;
+
+        if self.advance_if_is(&TokenType::QuestionMarkQuestionMark) {
+            let op: Token = self.previous().clone();
+            let right: Expression = self.coalesce()?;
+
+            expr = Expression::Logical {
+                left: Box::new(expr),
+                op: op,
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expr)
+    }
+
     fn or(&mut self) -> Result<Expression, ()> {
         let mut expr: Expression = self.and()?;
 
@@ -336,7 +1629,83 @@ impl Parser {
 
             Ok(right)
         } else {
-            Ok(self.primary()?)
+            Ok(self.call()?)
+        }
+    }
+
+    fn call(&mut self) -> Result<Expression, ()> {
+        let mut expr: Expression = self.primary()?;
+
+        loop {
+            if self.advance_if_is(&TokenType::Period) {
+                expr = Expression::Get {
+                    object: Box::new(expr),
+                    name: self.property()?,
+                };
+                continue;
+            }
+
+            if self.advance_if_is(&TokenType::QuestionMarkPeriod) {
+                expr = Expression::SafeGet {
+                    object: Box::new(expr),
+                    name: self.property()?,
+                };
+                continue;
+            }
+
+            if !self.advance_if_is(&TokenType::ParenthesisOpen) {
+                break;
+            }
+
+            let paren: Token = self.previous().clone();
+            let mut args: Vec<Expression> = Vec::new();
+            let mut named: Vec<(Token, Expression)> = Vec::new();
+
+            if !self.is_token(&TokenType::ParenthesisClose) {
+                loop {
+                    if self.is_token(&IDENTIFIER) && self.peek_next().token_type == TokenType::Equal
+                    {
+                        let name: Token = self.advance().clone();
+                        self.advance(); // Consume "=" token.
+                        named.push((name, self.expression()?));
+                    } else if named.is_empty() {
+                        args.push(self.expression()?);
+                    } else {
+                        parser_error(self.peek().line, EXPECT_NAMED_ARGUMENT.to_string());
+                        return Err(());
+                    }
+
+                    if !self.advance_if_is(&TokenType::Comma) {
+                        break;
+                    }
+                }
+            }
+
+            if !self.advance_if_is(&TokenType::ParenthesisClose) {
+                parser_error(
+                    self.peek().line,
+                    EXPECT_CLOSE_PARENTHESIS_ARGUMENTS.to_string(),
+                );
+                return Err(());
+            }
+
+            expr = Expression::Call {
+                callee: Box::new(expr),
+                paren: paren,
+                args: args,
+                named: named,
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn property(&mut self) -> Result<Token, ()> {
+        if self.advance_if_is(&IDENTIFIER) {
+            Ok(self.previous().clone())
+        } else {
+            parser_error(self.peek().line, EXPECT_PROPERTY_IDENTIFIER.to_string());
+            Err(())
         }
     }
 
@@ -353,6 +1722,22 @@ impl Parser {
             };
 
             Ok(expr)
+        } else if self.advance_if_is(&TokenType::Self_) {
+            let keyword: Token = self.previous().clone();
+
+            if self.classes.is_empty() {
+                parser_error(keyword.line, SELF_OUTSIDE_CLASS.to_string());
+                return Err(());
+            }
+
+            if self.static_ {
+                parser_error(keyword.line, SELF_IN_STATIC_METHOD.to_string());
+                return Err(());
+            }
+
+            Ok(Expression::Self_ { keyword: keyword })
+        } else if self.advance_if_is(&TokenType::Super) {
+            self.super_()
         } else if self.advance_if_is(&TokenType::ParenthesisOpen) {
             let mut expr: Expression = self.expression()?;
 
@@ -372,6 +1757,44 @@ impl Parser {
         }
     }
 
+    fn super_(&mut self) -> Result<Expression, ()> {
+        let keyword: Token = self.previous().clone();
+
+        match self.classes.last() {
+            Some(true) => (),
+            _ => {
+                parser_error(keyword.line, SUPER_OUTSIDE_SUBCLASS.to_string());
+                return Err(());
+            }
+        }
+
+        if self.static_ {
+            parser_error(keyword.line, SELF_IN_STATIC_METHOD.to_string());
+            return Err(());
+        }
+
+        // "super.method" picks a method, "super(...)" calls the overridden one.
+        let method: Token = if self.advance_if_is(&TokenType::Period) {
+            self.property()?
+        } else if self.is_token(&TokenType::ParenthesisOpen) {
+            match &self.method {
+                Some(m) => m.clone(),
+                None => {
+                    parser_error(keyword.line, SUPER_OUTSIDE_METHOD.to_string());
+                    return Err(());
+                }
+            }
+        } else {
+            parser_error(self.peek().line, EXPECT_SUPER_ACCESS.to_string());
+            return Err(());
+        };
+
+        Ok(Expression::Super {
+            keyword: keyword,
+            method: method,
+        })
+    }
+
     // Synchronize to a state that we expect everything to be okay.
     // This is used after some syntax error in code.
     fn synchronize(&mut self) {
@@ -427,11 +1850,36 @@ impl Parser {
         false
     }
 
+    // Skip empty lines and check if the next line is at that indentation.
+    fn is_line_at(&mut self, level: u8) -> bool {
+        while self.is_token(&TokenType::Newline) {
+            self.advance(); // Consume empty line.
+        }
+
+        self.peek().token_type == TokenType::Indent(level)
+    }
+
+    // Check if an "else" continues the statement at the current scope.
+    fn is_else(&self) -> bool {
+        match (&self.peek().token_type, &self.peek_next().token_type) {
+            (TokenType::Indent(level), TokenType::Else) => *level == self.level,
+            _ => false,
+        }
+    }
+
     // Get current token.
     fn peek(&self) -> &Token {
         self.tokens.get(self.current).unwrap()
     }
 
+    // Get next token.
+    fn peek_next(&self) -> &Token {
+        match self.tokens.get(self.current + 1) {
+            Some(t) => t,
+            None => self.peek(),
+        }
+    }
+
     // Get previous token.
     fn previous(&self) -> &Token {
         self.tokens.get(self.current - 1).unwrap()
@@ -1,9 +1,107 @@
 pub const EXPECT_CLOSE_PARENTHESIS: &str = "Expect ')' after expression.";
+pub const EXPECT_CLOSE_PARENTHESIS_ARGUMENTS: &str = "Expect ')' after arguments.";
+pub const EXPECT_CLOSE_PARENTHESIS_PATTERNS: &str = "Expect ')' after patterns.";
+pub const EXPECT_CLOSE_PARENTHESIS_PARAMETERS: &str = "Expect ')' after parameters.";
+pub const EXPECT_OPEN_PARENTHESIS_PARAMETERS: &str = "Expect '(' after function name.";
 pub const EXPECT_EXPRESSION: &str = "Expect expression.";
 pub const EXPECT_NEWLINE: &str = "Expect newline.";
 pub const EXPECT_INDENT: &str = "Expect indentation.";
 pub const EXPECT_COLON: &str = "Expect colon to start new scope.";
 pub const EXPECT_VAR_IDENTIFIER: &str = "Expect name after 'var'.";
+pub const EXPECT_CONST_IDENTIFIER: &str = "Expect name after 'const'.";
+pub const EXPECT_CONST_VALUE: &str = "Expect '=' and a value after constant name.";
+pub const EXPECT_ENUM_IDENTIFIER: &str = "Expect name after 'enum'.";
+pub const EXPECT_VARIANT_IDENTIFIER: &str = "Expect variant name.";
+pub const EXPECT_PROPERTY_IDENTIFIER: &str = "Expect property name after '.'.";
+pub const EXPECT_STRUCT_IDENTIFIER: &str = "Expect name after 'struct'.";
+pub const EXPECT_STRUCT_FIELD: &str = "Expect 'var' declaring a field.";
+pub const EXPECT_FIELD_IDENTIFIER: &str = "Expect field name after 'var'.";
+pub const EXPECT_ACCESSOR: &str = "Expect 'get' or 'set' accessor.";
+pub const EXPECT_SETTER_PARAMETER: &str = "Expect exactly one parameter in 'set' accessor.";
+pub const EXPECT_NAMED_ARGUMENT: &str = "Expect named argument after a named argument.";
+pub const EXPECT_CLASS_IDENTIFIER: &str = "Expect name after 'class'.";
+pub const EXPECT_SINGLETON_IDENTIFIER: &str = "Expect name after 'singleton'.";
+pub const SINGLETON_OUTSIDE_TOP_LEVEL: &str = "Only top-level declarations can be singletons.";
+pub const SINGLETON_CONSTRUCTOR_PARAMETERS: &str = "Singleton constructor can't have parameters.";
+pub const EXPECT_SUPERCLASS_IDENTIFIER: &str = "Expect superclass name after 'extends'.";
+pub const EXPECT_INTERFACE_IDENTIFIER: &str = "Expect interface name.";
+pub const EXPECT_SIGNATURE: &str = "Expect method signature in interface body.";
+pub const EXPECT_MODIFIED_MEMBER: &str = "Expect 'var' or 'func' after modifiers.";
+pub const STATIC_FIELD_ACCESSORS: &str = "Static fields can't have accessors.";
+pub const SELF_IN_STATIC_METHOD: &str = "Can't use 'self' or 'super' in a static method.";
+pub const EXPECT_CLASS_MEMBER: &str = "Expect field, method, signal or constructor in class body.";
+pub const EXPECT_SIGNAL_IDENTIFIER: &str = "Expect name after 'signal'.";
+pub const EXPECT_SIGNAL_CALL: &str = "Expect signal call after 'emit'.";
+pub const EXPECT_SUPER_ACCESS: &str = "Expect '.' or '(' after 'super'.";
+pub const SELF_OUTSIDE_CLASS: &str = "Can't use 'self' outside of a class.";
+pub const SUPER_OUTSIDE_SUBCLASS: &str = "Can't use 'super' in a class without superclass.";
+pub const SUPER_OUTSIDE_METHOD: &str = "Can't call 'super' outside of a method.";
+pub const RETURN_VALUE_IN_CONSTRUCTOR: &str = "Can't return a value from a constructor.";
+pub const RETURN_VALUE_IN_DESTRUCTOR: &str = "Can't return a value from a destructor.";
+pub const EXPECT_FUNC_IDENTIFIER: &str = "Expect name after 'func'.";
+pub const EXPECT_CORO_IDENTIFIER: &str = "Expect name after 'coro'.";
+pub const EXPECT_PARAMETER_IDENTIFIER: &str = "Expect parameter name.";
+pub const EXPECT_FOR_IDENTIFIER: &str = "Expect name after 'for'.";
+pub const EXPECT_IN: &str = "Expect 'in' after loop variable.";
+pub const LOOP_WITHOUT_BREAK: &str = "'loop' has no reachable 'break'.";
+pub const EXPECT_PATTERN: &str = "Expect pattern.";
+pub const BREAK_OUTSIDE_LOOP: &str = "Can't use 'break' outside of a loop.";
+pub const CONTINUE_OUTSIDE_LOOP: &str = "Can't use 'continue' outside of a loop.";
+pub const PUBLIC_OUTSIDE_TOP_LEVEL: &str = "Only top-level declarations can be public.";
+pub const EXPECT_PUBLIC_DECLARATION: &str = "Expect declaration after 'public'.";
+pub const EXPECT_MODULE_IDENTIFIER: &str = "Expect module name.";
+pub const EXPECT_IMPORT: &str = "Expect 'import' after module name.";
+pub const EXPECT_IMPORT_IDENTIFIER: &str = "Expect name to import.";
+pub const EXPECT_ALIAS_IDENTIFIER: &str = "Expect name after 'as'.";
+pub const EXPECT_TYPE: &str = "Expect type: 'bool', 'int', 'float', 'char', 'str' or 'void'.";
+pub const VOID_OUTSIDE_RETURN: &str = "Only return types can be 'void'.";
+pub const UNINITIALIZED_NOT_NULLABLE: &str =
+    "Variables and fields without a value start as null, so their type must be nullable.";
+pub const NULLABLE_VOID: &str = "Type 'void' can't be nullable.";
+pub const AWAIT_ANNOTATED: &str = "Variables receiving 'await' can't have a type.";
+pub const RETURN_OUTSIDE_FUNCTION: &str = "Can't use 'return' outside of a function.";
+pub const YIELD_OUTSIDE_COROUTINE: &str = "Can't use 'yield' outside of a coroutine.";
+pub const AWAIT_OUTSIDE_COROUTINE: &str = "Can't use 'await' outside of a coroutine.";
+
+pub fn variant_duplicated(name: &str) -> String {
+    format!("Variant '{}' is already declared", name)
+}
+
+pub fn field_duplicated(name: &str) -> String {
+    format!("Field '{}' is already declared", name)
+}
+
+pub fn class_inherits_itself(name: &str) -> String {
+    format!("Class '{}' can't extend itself", name)
+}
+
+pub fn interface_duplicated(name: &str) -> String {
+    format!("Interface '{}' is already implemented", name)
+}
+
+pub fn accessor_duplicated(name: &str) -> String {
+    format!("Accessor '{}' is already declared", name)
+}
+
+pub fn modifier_duplicated(name: &str) -> String {
+    format!("Modifier '{}' is already used", name)
+}
+
+pub fn method_duplicated(name: &str) -> String {
+    format!("Method '{}' is already declared", name)
+}
+
+pub fn signal_duplicated(name: &str) -> String {
+    format!("Signal '{}' is already declared", name)
+}
+
+pub fn match_missing_variants(name: &str, missing: &Vec<String>) -> String {
+    format!(
+        "'match' over enum '{}' doesn't handle: {}",
+        name,
+        missing.join(", ")
+    )
+}
 
 pub fn invalid_var_on_assignment(op: &str) -> String {
     format!("Invalid variable before '{}'", op)
@@ -0,0 +1,106 @@
+use super::error::match_missing_variants;
+use super::expression::Expression;
+use super::pattern::{Arm, Pattern};
+use super::statement::Statement;
+use super::utility::walk;
+use crate::error::parser_warning;
+use crate::tokenizer::token::Token;
+use std::collections::HashMap;
+
+// Warn about matches over an enum that don't handle all its variants.
+pub fn check_exhaustiveness(statements: &Vec<Statement>) {
+    let mut enums: HashMap<String, Vec<String>> = HashMap::new();
+
+    for stmt in statements {
+        walk(stmt, &mut |s| match s {
+            Statement::Enum { id, variants } => {
+                let names: Vec<String> = variants.iter().map(|v| v.id.lexeme.clone()).collect();
+                enums.insert(id.lexeme.clone(), names);
+            }
+            _ => (),
+        });
+    }
+
+    for stmt in statements {
+        walk(stmt, &mut |s| match s {
+            Statement::Match {
+                token,
+                expr: _,
+                arms,
+            } => check_match(token, arms, &enums),
+            _ => (),
+        });
+    }
+}
+
+fn check_match(token: &Token, arms: &Vec<Arm>, enums: &HashMap<String, Vec<String>>) {
+    let mut enumeration: Option<&String> = None;
+    let mut covered: Vec<&String> = Vec::new();
+
+    for arm in arms {
+        let guarded: bool = arm.guard.is_some();
+
+        let (path, fields) = match &arm.pattern {
+            Pattern::Wildcard | Pattern::Binding { id: _ } => {
+                if guarded {
+                    continue;
+                }
+
+                return; // Catch all arm.
+            }
+            Pattern::Value { expr } => (expr, None),
+            Pattern::Variant {
+                path,
+                paren: _,
+                fields,
+            } => (path, Some(fields)),
+            Pattern::Range {
+                start: _,
+                op: _,
+                end: _,
+            } => continue,
+        };
+
+        let (name, variant) = match &**path {
+            Expression::Get { object, name } => match &**object {
+                Expression::Variable { id } => (&id.lexeme, &name.lexeme),
+                _ => continue,
+            },
+            _ => continue,
+        };
+
+        if !enums.contains_key(name) {
+            continue;
+        }
+
+        enumeration = Some(name);
+
+        // Only arms that always match their variant cover it.
+        let irrefutable: bool = match fields {
+            Some(f) => f.iter().all(|p| match p {
+                Pattern::Wildcard | Pattern::Binding { id: _ } => true,
+                _ => false,
+            }),
+            None => true,
+        };
+
+        if !guarded && irrefutable {
+            covered.push(variant);
+        }
+    }
+
+    let name: &String = match enumeration {
+        Some(e) => e,
+        None => return,
+    };
+
+    let missing: Vec<String> = enums[name]
+        .iter()
+        .filter(|v| !covered.contains(v))
+        .cloned()
+        .collect();
+
+    if !missing.is_empty() {
+        parser_warning(token.line, match_missing_variants(name, &missing));
+    }
+}
@@ -7,6 +7,20 @@ pub enum Expression {
         right: Box<Expression>,
     },
 
+    Set {
+        object: Box<Expression>,
+        name: Token,
+        op: Token,
+        right: Box<Expression>,
+    },
+
+    Range {
+        start: Box<Expression>,
+        op: Token,
+        end: Box<Expression>,
+        step: Option<Box<Expression>>,
+    },
+
     Logical {
         left: Box<Expression>,
         op: Token,
@@ -24,6 +38,33 @@ pub enum Expression {
         right: Box<Expression>,
     },
 
+    Call {
+        callee: Box<Expression>,
+        paren: Token,
+        args: Vec<Expression>,
+        named: Vec<(Token, Expression)>,
+    },
+
+    Get {
+        object: Box<Expression>,
+        name: Token,
+    },
+
+    // Null when object is null.
+    SafeGet {
+        object: Box<Expression>,
+        name: Token,
+    },
+
+    Self_ {
+        keyword: Token,
+    },
+
+    Super {
+        keyword: Token,
+        method: Token,
+    },
+
     Grouping {
         expr: Box<Expression>,
     },
@@ -43,6 +84,33 @@ impl Expression {
             Expression::Assignment { id, op, right } => {
                 format!("({} {} {})", id.lexeme, op.lexeme, right.to_string())
             }
+            Expression::Range {
+                start,
+                op,
+                end,
+                step,
+            } => match step {
+                Some(s) => format!(
+                    "({} {} {} {})",
+                    op.lexeme,
+                    start.to_string(),
+                    end.to_string(),
+                    s.to_string()
+                ),
+                None => format!("({} {} {})", op.lexeme, start.to_string(), end.to_string()),
+            },
+            Expression::Set {
+                object,
+                name,
+                op,
+                right,
+            } => format!(
+                "({}.{} {} {})",
+                object.to_string(),
+                name.lexeme,
+                op.lexeme,
+                right.to_string()
+            ),
             Expression::Logical { left, op, right } => {
                 format!("({} {} {})", op.lexeme, left.to_string(), right.to_string())
             }
@@ -52,6 +120,33 @@ impl Expression {
             Expression::Unary { op, right } => {
                 format!("({} {})", op.lexeme, right.to_string())
             }
+            Expression::Call {
+                callee,
+                paren: _,
+                args,
+                named,
+            } => {
+                let mut call: String = format!("(call {}", callee.to_string());
+
+                for arg in args {
+                    call.push_str(format!(" {}", arg.to_string()).as_str());
+                }
+
+                for (name, arg) in named {
+                    call.push_str(format!(" {}={}", name.lexeme, arg.to_string()).as_str());
+                }
+
+                call.push(')');
+                call
+            }
+            Expression::Get { object, name } => {
+                format!("(get {} {})", object.to_string(), name.lexeme)
+            }
+            Expression::SafeGet { object, name } => {
+                format!("(get? {} {})", object.to_string(), name.lexeme)
+            }
+            Expression::Self_ { keyword: _ } => "self".to_string(),
+            Expression::Super { keyword: _, method } => format!("(super {})", method.lexeme),
             Expression::Grouping { expr } => format!("(group {})", expr.to_string()),
             Expression::Variable { id } => format!("(variable {})", id.lexeme.clone()),
             Expression::Literal { token } => token.lexeme.clone(),
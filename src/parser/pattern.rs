@@ -0,0 +1,73 @@
+use super::expression::Expression;
+use super::statement::Statement;
+use crate::tokenizer::token::Token;
+use std::rc::Rc;
+
+pub enum Pattern {
+    Wildcard,
+
+    Binding {
+        id: Token,
+    },
+
+    Value {
+        expr: Box<Expression>,
+    },
+
+    Range {
+        start: Box<Expression>,
+        op: Token,
+        end: Box<Expression>,
+    },
+
+    Variant {
+        path: Box<Expression>,
+        paren: Token,
+        fields: Vec<Pattern>,
+    },
+}
+
+pub struct Arm {
+    pub pattern: Pattern,
+    pub guard: Option<Box<Expression>>,
+    pub statement: Rc<Statement>,
+}
+
+impl Pattern {
+    pub fn to_string(&self) -> String {
+        match self {
+            Pattern::Wildcard => "_".to_string(),
+            Pattern::Binding { id } => format!("(bind {})", id.lexeme),
+            Pattern::Value { expr } => expr.to_string(),
+            Pattern::Range { start, op, end } => {
+                format!("({} {} {})", op.lexeme, start.to_string(), end.to_string())
+            }
+            Pattern::Variant {
+                path,
+                paren: _,
+                fields,
+            } => {
+                let fields: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+                format!("(variant {} {})", path.to_string(), fields.join(" "))
+            }
+        }
+    }
+}
+
+impl Arm {
+    pub fn to_string(&self) -> String {
+        match &self.guard {
+            Some(g) => format!(
+                "(arm {} when {} {})",
+                self.pattern.to_string(),
+                g.to_string(),
+                self.statement.to_string()
+            ),
+            None => format!(
+                "(arm {} {})",
+                self.pattern.to_string(),
+                self.statement.to_string()
+            ),
+        }
+    }
+}
@@ -1,34 +1,146 @@
 use super::expression::Expression;
+use super::pattern::Arm;
+use super::types::Type;
 use crate::tokenizer::token::Token;
+use std::rc::Rc;
 
 pub enum Statement {
     Var {
         identifier: Token,
+        annotation: Option<Type>,
     },
 
     VarAssign {
         identifier: Token,
+        annotation: Option<Type>,
         expr: Box<Expression>,
     },
 
+    Func {
+        id: Token,
+        params: Vec<Param>,
+        output: Option<Type>, // Return type.
+        body: Rc<Statement>,
+    },
+
+    Coro {
+        id: Token,
+        params: Vec<Param>,
+        output: Option<Type>, // Return type.
+        body: Rc<Statement>,
+    },
+
+    Const {
+        identifier: Token,
+        annotation: Option<Type>,
+        expr: Box<Expression>,
+    },
+
+    Enum {
+        id: Token,
+        variants: Vec<EnumVariant>,
+    },
+
+    Struct {
+        id: Token,
+        fields: Vec<Field>,
+    },
+
+    Class {
+        singleton: bool,
+        id: Token,
+        superclass: Option<Token>,
+        interfaces: Vec<Token>,
+        fields: Vec<Field>,
+        methods: Vec<Method>,
+        signals: Vec<Signature>,
+    },
+
+    Interface {
+        id: Token,
+        signatures: Vec<Signature>,
+    },
+
+    Import {
+        keyword: Token,
+        path: Vec<Token>,
+        alias: Option<Token>,
+    },
+
+    FromImport {
+        keyword: Token,
+        path: Vec<Token>,
+        names: Vec<(Token, Option<Token>)>, // With their aliases.
+    },
+
+    Public {
+        declaration: Box<Statement>,
+    },
+
     Print {
         expr: Box<Expression>,
     },
 
+    Emit {
+        keyword: Token,
+        signal: Box<Expression>,
+        args: Vec<Expression>,
+    },
+
     Block {
-        stmts: Vec<Statement>,
+        stmts: Vec<Rc<Statement>>,
         level: u8,
     },
 
     If {
         condition: Box<Expression>,
-        statement: Box<Statement>,
+        statement: Rc<Statement>,
     },
 
     IfElse {
         condition: Box<Expression>,
-        if_statement: Box<Statement>,
-        else_statement: Box<Statement>,
+        if_statement: Rc<Statement>,
+        else_statement: Rc<Statement>,
+    },
+
+    While {
+        condition: Box<Expression>,
+        statement: Rc<Statement>,
+    },
+
+    For {
+        id: Token,
+        iterable: Box<Expression>,
+        statement: Rc<Statement>,
+    },
+
+    Loop {
+        statement: Rc<Statement>,
+    },
+
+    Match {
+        token: Token,
+        expr: Box<Expression>,
+        arms: Vec<Arm>,
+    },
+
+    Break,
+
+    Continue,
+
+    Return {
+        expr: Option<Box<Expression>>,
+    },
+
+    Yield {
+        keyword: Token,
+        expr: Option<Box<Expression>>,
+    },
+
+    Await {
+        keyword: Token,
+        expr: Box<Expression>,
+        id: Option<Token>, // Variable declared with the awaited value.
     },
 
     Expr {
@@ -36,14 +148,200 @@ pub enum Statement {
     },
 }
 
+#[derive(Clone)]
+pub struct EnumVariant {
+    pub id: Token,
+    pub fields: Vec<Token>,
+}
+
+#[derive(Clone)]
+pub struct Field {
+    pub public: bool,
+    pub static_: bool, // Escape conflict with static
+    pub id: Token,
+    pub annotation: Option<Type>,
+    pub default: Option<Rc<Expression>>,
+    pub getter: Option<Rc<Statement>>,
+    pub setter: Option<(Token, Rc<Statement>)>, // Parameter receiving the new value.
+}
+
+#[derive(Clone)]
+pub struct Method {
+    pub public: bool,
+    pub static_: bool, // Escape conflict with static
+    pub coroutine: bool,
+    pub id: Token,
+    pub params: Vec<Param>,
+    pub output: Option<Type>,
+    pub body: Rc<Statement>,
+}
+
+#[derive(Clone)]
+pub struct Signature {
+    pub id: Token,
+    pub params: Vec<Param>,
+    pub output: Option<Type>,
+}
+
+#[derive(Clone)]
+pub struct Param {
+    pub id: Token,
+    pub annotation: Option<Type>,
+}
+
 impl Statement {
     pub fn to_string(&self) -> String {
         match self {
-            Statement::Var { identifier } => format!("(var {})", identifier.lexeme),
-            Statement::VarAssign { identifier, expr } => {
-                format!("(var {} {})", identifier.lexeme, expr.to_string())
+            Statement::Var {
+                identifier,
+                annotation,
+            } => format!("(var {})", annotated_to_string(identifier, annotation)),
+            Statement::VarAssign {
+                identifier,
+                annotation,
+                expr,
+            } => format!(
+                "(var {} {})",
+                annotated_to_string(identifier, annotation),
+                expr.to_string()
+            ),
+            Statement::Func {
+                id,
+                params,
+                output,
+                body,
+            } => format!(
+                "(func {} ({}){} {})",
+                id.lexeme,
+                params_to_string(params),
+                output_to_string(output),
+                body.to_string()
+            ),
+            Statement::Coro {
+                id,
+                params,
+                output,
+                body,
+            } => format!(
+                "(coro {} ({}){} {})",
+                id.lexeme,
+                params_to_string(params),
+                output_to_string(output),
+                body.to_string()
+            ),
+            Statement::Const {
+                identifier,
+                annotation,
+                expr,
+            } => format!(
+                "(const {} {})",
+                annotated_to_string(identifier, annotation),
+                expr.to_string()
+            ),
+            Statement::Enum { id, variants } => {
+                let variants: Vec<String> = variants.iter().map(|v| v.to_string()).collect();
+                format!("(enum {} {})", id.lexeme, variants.join(" "))
             }
+            Statement::Struct { id, fields } => {
+                let fields: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+                format!("(struct {} {})", id.lexeme, fields.join(" "))
+            }
+            Statement::Class {
+                singleton,
+                id,
+                superclass,
+                interfaces,
+                fields,
+                methods,
+                signals,
+            } => {
+                let keyword: &str = if *singleton { "singleton" } else { "class" };
+                let mut class: String = format!("({} {}", keyword, id.lexeme);
+
+                match superclass {
+                    Some(s) => class.push_str(format!(" extends {}", s.lexeme).as_str()),
+                    None => (),
+                }
+
+                for interface in interfaces {
+                    class.push_str(format!(" implements {}", interface.lexeme).as_str());
+                }
+
+                for field in fields {
+                    class.push_str(format!(" {}", field.to_string()).as_str());
+                }
+
+                for method in methods {
+                    class.push_str(format!(" {}", method.to_string()).as_str());
+                }
+
+                for signal in signals {
+                    class.push_str(
+                        format!(
+                            " (signal {} ({}))",
+                            signal.id.lexeme,
+                            params_to_string(&signal.params)
+                        )
+                        .as_str(),
+                    );
+                }
+
+                class.push(')');
+                class
+            }
+            Statement::Interface { id, signatures } => {
+                let mut interface: String = format!("(interface {}", id.lexeme);
+
+                for signature in signatures {
+                    interface.push_str(format!(" {}", signature.to_string()).as_str());
+                }
+
+                interface.push(')');
+                interface
+            }
+            Statement::Import {
+                keyword: _,
+                path,
+                alias,
+            } => match alias {
+                Some(a) => format!("(import {} as {})", path_to_string(path), a.lexeme),
+                None => format!("(import {})", path_to_string(path)),
+            },
+            Statement::FromImport {
+                keyword: _,
+                path,
+                names,
+            } => {
+                let mut import: String = format!("(from {} import", path_to_string(path));
+
+                for (name, alias) in names {
+                    match alias {
+                        Some(a) => {
+                            import.push_str(format!(" ({} as {})", name.lexeme, a.lexeme).as_str())
+                        }
+                        None => import.push_str(format!(" {}", name.lexeme).as_str()),
+                    }
+                }
+
+                import.push(')');
+                import
+            }
+            Statement::Public { declaration } => format!("(public {})", declaration.to_string()),
             Statement::Print { expr } => format!("(print {})", (*expr).to_string()),
+            Statement::Emit {
+                keyword: _,
+                signal,
+                args,
+            } => {
+                let mut emit: String = format!("(emit {}", signal.to_string());
+
+                for arg in args {
+                    emit.push_str(format!(" {}", arg.to_string()).as_str());
+                }
+
+                emit.push(')');
+                emit
+            }
             Statement::Block { stmts, level } => {
                 let tabs: String = "\t".repeat((*level) as usize);
                 let mut block: String = format!("(block-{}", *level);
@@ -69,7 +367,166 @@ impl Statement {
                 if_statement.to_string(),
                 else_statement.to_string()
             ),
+            Statement::While {
+                condition,
+                statement,
+            } => format!(
+                "(while {} {})",
+                condition.to_string(),
+                statement.to_string()
+            ),
+            Statement::For {
+                id,
+                iterable,
+                statement,
+            } => format!(
+                "(for {} {} {})",
+                id.lexeme,
+                iterable.to_string(),
+                statement.to_string()
+            ),
+            Statement::Loop { statement } => format!("(loop {})", statement.to_string()),
+            Statement::Match {
+                token: _,
+                expr,
+                arms,
+            } => {
+                let mut string: String = format!("(match {}", expr.to_string());
+
+                for arm in arms {
+                    string.push_str(format!(" {}", arm.to_string()).as_str());
+                }
+
+                string.push(')');
+                string
+            }
+            Statement::Break => "(break)".to_string(),
+            Statement::Continue => "(continue)".to_string(),
+            Statement::Return { expr } => match expr {
+                Some(e) => format!("(return {})", e.to_string()),
+                None => "(return)".to_string(),
+            },
+            Statement::Yield { keyword: _, expr } => match expr {
+                Some(e) => format!("(yield {})", e.to_string()),
+                None => "(yield)".to_string(),
+            },
+            Statement::Await {
+                keyword: _,
+                expr,
+                id,
+            } => match id {
+                Some(i) => format!("(var {} (await {}))", i.lexeme, expr.to_string()),
+                None => format!("(await {})", expr.to_string()),
+            },
             Statement::Expr { expr } => format!("(expr {})", (*expr).to_string()),
         }
     }
 }
+
+impl EnumVariant {
+    pub fn to_string(&self) -> String {
+        if self.fields.is_empty() {
+            return self.id.lexeme.clone();
+        }
+
+        let fields: Vec<String> = self.fields.iter().map(|f| f.lexeme.clone()).collect();
+        format!("{}({})", self.id.lexeme, fields.join(" "))
+    }
+}
+
+impl Field {
+    pub fn to_string(&self) -> String {
+        let modifiers: String = modifiers_to_string(self.public, self.static_);
+        let id: String = annotated_to_string(&self.id, &self.annotation);
+        let mut field: String = match &self.default {
+            Some(d) => format!("({}var {} {}", modifiers, id, d.to_string()),
+            None => format!("({}var {}", modifiers, id),
+        };
+
+        match &self.getter {
+            Some(g) => field.push_str(format!(" (get {})", g.to_string()).as_str()),
+            None => (),
+        }
+
+        match &self.setter {
+            Some((p, s)) => {
+                field.push_str(format!(" (set ({}) {})", p.lexeme, s.to_string()).as_str())
+            }
+            None => (),
+        }
+
+        field.push(')');
+        field
+    }
+}
+
+impl Method {
+    pub fn to_string(&self) -> String {
+        let keyword: &str = if self.coroutine { "coro" } else { "method" };
+        format!(
+            "({}{} {} ({}){} {})",
+            modifiers_to_string(self.public, self.static_),
+            keyword,
+            self.id.lexeme,
+            params_to_string(&self.params),
+            output_to_string(&self.output),
+            self.body.to_string()
+        )
+    }
+}
+
+impl Signature {
+    pub fn arity(&self) -> usize {
+        self.params.len()
+    }
+
+    pub fn to_string(&self) -> String {
+        format!(
+            "(signature {} ({}){})",
+            self.id.lexeme,
+            params_to_string(&self.params),
+            output_to_string(&self.output)
+        )
+    }
+}
+
+fn annotated_to_string(id: &Token, annotation: &Option<Type>) -> String {
+    match annotation {
+        Some(t) => format!("{}: {}", id.lexeme, t.to_string()),
+        None => id.lexeme.clone(),
+    }
+}
+
+fn params_to_string(params: &Vec<Param>) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .map(|p| annotated_to_string(&p.id, &p.annotation))
+        .collect();
+    params.join(" ")
+}
+
+fn output_to_string(output: &Option<Type>) -> String {
+    match output {
+        Some(t) => format!(" -> {}", t.to_string()),
+        None => String::new(),
+    }
+}
+
+pub fn path_to_string(path: &Vec<Token>) -> String {
+    let path: Vec<String> = path.iter().map(|p| p.lexeme.clone()).collect();
+    path.join(".")
+}
+
+fn modifiers_to_string(public: bool, static_: bool) -> String {
+    let mut modifiers: String = String::new();
+
+    if public {
+        modifiers.push_str("public ");
+    }
+
+    if static_ {
+        modifiers.push_str("static ");
+    }
+
+    modifiers
+}
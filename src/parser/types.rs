@@ -0,0 +1,41 @@
+use crate::tokenizer::token_type::TokenType;
+
+// Types given to variables, parameters and return values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Boolean,
+    Integer,
+    Floating,
+    Character,
+    String_,
+    Void, // Only as return type.
+    Nullable(Box<Type>),
+    Unknown, // Only for the checker, inside nullable types of values known when running.
+}
+
+impl Type {
+    pub fn from(token_type: &TokenType) -> Option<Type> {
+        match token_type {
+            TokenType::Bool => Some(Type::Boolean),
+            TokenType::Int => Some(Type::Integer),
+            TokenType::Float => Some(Type::Floating),
+            TokenType::Char => Some(Type::Character),
+            TokenType::Str => Some(Type::String_),
+            TokenType::Void => Some(Type::Void),
+            _ => None,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            Type::Boolean => "bool".to_string(),
+            Type::Integer => "int".to_string(),
+            Type::Floating => "float".to_string(),
+            Type::Character => "char".to_string(),
+            Type::String_ => "str".to_string(),
+            Type::Void => "void".to_string(),
+            Type::Nullable(t) => format!("{}?", t.to_string()),
+            Type::Unknown => "unknown".to_string(),
+        }
+    }
+}
@@ -1,10 +1,10 @@
+use super::statement::{Field, Statement};
+use crate::tokenizer::token::Token;
 use crate::tokenizer::token_type::TokenType;
 use crate::tokenizer::token_type::TokenType::*;
 
 pub const IDENTIFIER: TokenType = Identifier(String::new());
 
-pub const INDENT: TokenType = Indent(0);
-
 pub const ASSIGNMENTS: [TokenType; 12] = [
     Equal,
     PlusEqual,
@@ -20,16 +20,20 @@ pub const ASSIGNMENTS: [TokenType; 12] = [
     LessLessEqual,
 ];
 
+pub const RANGES: [TokenType; 2] = [PeriodPeriod, PeriodPeriodEqual];
+
 pub const EQUALITIES: [TokenType; 2] = [EqualEqual, NotEqual];
 
-pub const COMPARASIONS: [TokenType; 4] = [Greater, Less, GreaterEqual, LessEqual];
+pub const COMPARASIONS: [TokenType; 5] = [Greater, Less, GreaterEqual, LessEqual, Is];
 
 pub const TERMS: [TokenType; 5] = [Plus, Minus, Ampersand, Pipe, Caret];
 
 pub const FACTORIZATIONS: [TokenType; 6] =
     [Star, Slash, Percentage, StarStar, GreaterGreater, LessLess];
 
-pub const UNARIES: [TokenType; 3] = [Minus, Not, ExclamationMark];
+pub const UNARIES: [TokenType; 4] = [Minus, Not, ExclamationMark, Resume];
+
+pub const MODIFIERS: [TokenType; 2] = [Public, Static];
 
 pub const LITERALS: [TokenType; 6] = [
     Boolean(false),
@@ -48,3 +52,174 @@ pub fn is_statement(token_type: &TokenType) -> bool {
         _ => false,
     }
 }
+
+// Check if a loop body has a "break" that can leave it.
+// Breaks inside nested loops belong to them, so they don't count.
+pub fn is_breakable(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Break => true,
+        Statement::Return { expr: _ } => true,
+        Statement::Block { stmts, level: _ } => {
+            for s in stmts {
+                match &**s {
+                    Statement::Continue => return false, // Everything after is unreachable.
+                    _ => {
+                        if is_breakable(s) {
+                            return true;
+                        }
+                    }
+                }
+            }
+
+            false
+        }
+        Statement::If {
+            condition: _,
+            statement,
+        } => is_breakable(statement),
+        Statement::IfElse {
+            condition: _,
+            if_statement,
+            else_statement,
+        } => is_breakable(if_statement) || is_breakable(else_statement),
+        Statement::Match {
+            token: _,
+            expr: _,
+            arms,
+        } => arms.iter().any(|arm| is_breakable(&arm.statement)),
+        _ => false,
+    }
+}
+
+// Name given by a declaration.
+pub fn declared_id(stmt: &Statement) -> Option<&Token> {
+    match stmt {
+        Statement::Var {
+            identifier,
+            annotation: _,
+        }
+        | Statement::VarAssign {
+            identifier,
+            annotation: _,
+            expr: _,
+        }
+        | Statement::Const {
+            identifier,
+            annotation: _,
+            expr: _,
+        } => Some(identifier),
+        Statement::Func {
+            id,
+            params: _,
+            output: _,
+            body: _,
+        }
+        | Statement::Coro {
+            id,
+            params: _,
+            output: _,
+            body: _,
+        }
+        | Statement::Enum { id, variants: _ }
+        | Statement::Struct { id, fields: _ }
+        | Statement::Interface { id, signatures: _ } => Some(id),
+        Statement::Class {
+            singleton: _,
+            id,
+            superclass: _,
+            interfaces: _,
+            fields: _,
+            methods: _,
+            signals: _,
+        } => Some(id),
+        _ => None,
+    }
+}
+
+// Visit the statement and every statement nested inside it.
+pub fn walk<'a>(stmt: &'a Statement, visit: &mut dyn FnMut(&'a Statement)) {
+    visit(stmt);
+
+    match stmt {
+        Statement::Block { stmts, level: _ } => {
+            for s in stmts {
+                walk(s, visit);
+            }
+        }
+        Statement::If {
+            condition: _,
+            statement,
+        }
+        | Statement::While {
+            condition: _,
+            statement,
+        }
+        | Statement::For {
+            id: _,
+            iterable: _,
+            statement,
+        }
+        | Statement::Loop { statement } => walk(statement, visit),
+        Statement::IfElse {
+            condition: _,
+            if_statement,
+            else_statement,
+        } => {
+            walk(if_statement, visit);
+            walk(else_statement, visit);
+        }
+        Statement::Func {
+            id: _,
+            params: _,
+            output: _,
+            body,
+        }
+        | Statement::Coro {
+            id: _,
+            params: _,
+            output: _,
+            body,
+        } => walk(body, visit),
+        Statement::Match {
+            token: _,
+            expr: _,
+            arms,
+        } => {
+            for arm in arms {
+                walk(&arm.statement, visit);
+            }
+        }
+        Statement::Class {
+            singleton: _,
+            id: _,
+            superclass: _,
+            interfaces: _,
+            fields,
+            methods,
+            signals: _,
+        } => {
+            walk_fields(fields, visit);
+
+            for method in methods {
+                walk(&method.body, visit);
+            }
+        }
+        Statement::Struct { id: _, fields } => walk_fields(fields, visit),
+        Statement::Public { declaration } => walk(declaration, visit),
+        _ => (),
+    }
+}
+
+fn walk_fields<'a>(fields: &'a Vec<Field>, visit: &mut dyn FnMut(&'a Statement)) {
+    for field in fields {
+        match &field.getter {
+            Some(g) => walk(g, visit),
+            None => (),
+        }
+
+        match &field.setter {
+            Some((_, s)) => walk(s, visit),
+            None => (),
+        }
+    }
+}
@@ -77,6 +77,9 @@ impl Tokenizer {
             '<' if self.is_followed_by("<=") => self.add_token(LessLessEqual, "<<="),
             '*' if self.is_followed_by("*=") => self.add_token(StarStarEqual, "**="),
 
+            // Range (3 chars)
+            '.' if self.is_followed_by(".=") => self.add_token(PeriodPeriodEqual, "..="),
+
             // Assignment (2 chars)
             '+' if self.is_followed_by("=") => self.add_token(PlusEqual, "+="),
             '-' if self.is_followed_by("=") => self.add_token(MinusEqual, "-="),
@@ -100,8 +103,14 @@ impl Tokenizer {
             // Math (2 chars)
             '*' if self.is_followed_by("*") => self.add_token(StarStar, "**"),
 
-            // RESERVED (2 chars)
+            // Range (2 chars)
             '.' if self.is_followed_by(".") => self.add_token(PeriodPeriod, ".."),
+
+            // Null safety (2 chars)
+            '?' if self.is_followed_by("?") => self.add_token(QuestionMarkQuestionMark, "??"),
+            '?' if self.is_followed_by(".") => self.add_token(QuestionMarkPeriod, "?."),
+
+            // RESERVED (2 chars)
             '-' if self.is_followed_by(">") => self.add_token(ForwardArrow, "->"),
 
             // Assignment
@@ -150,9 +159,11 @@ impl Tokenizer {
             ',' => self.add_token(Comma, ","),
             ':' => self.add_token(Colon, ":"),
             ';' => self.add_token(Semicolon, ";"),
-            '?' => self.add_token(QuestionMark, "?"),
             '_' => self.add_token(Underscore, "_"),
 
+            // Null safety
+            '?' => self.add_token(QuestionMark, "?"),
+
             // Ignored
             ' ' => (),
             '\r' => (),
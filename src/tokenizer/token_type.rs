@@ -108,6 +108,10 @@ pub enum TokenType {
     Super,
     Is,
 
+    // Range
+    PeriodPeriod,
+    PeriodPeriodEqual,
+
     // Open Close
     ParenthesisOpen,
     ParenthesisClose,
@@ -138,6 +142,11 @@ pub enum TokenType {
     // Typecasting
     To,
 
+    // Null safety
+    QuestionMark,
+    QuestionMarkQuestionMark,
+    QuestionMarkPeriod,
+
     // SPECIAL
     Identifier(String),
     Eof,
@@ -149,7 +158,5 @@ pub enum TokenType {
     Comma,
     Semicolon,
     Underscore,
-    QuestionMark,
-    PeriodPeriod,
     ForwardArrow,
 }
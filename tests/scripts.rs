@@ -0,0 +1,43 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// Each script in tests/scripts is run and its output compared with the file next to
+// it ending in ".out". Scripts starting with "check_" are run with "lai check".
+#[test]
+fn scripts() {
+    let directory: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scripts");
+    let mut scripts: Vec<PathBuf> = fs::read_dir(&directory)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|e| e == "lai"))
+        .collect();
+    let mut failures: Vec<String> = Vec::new();
+
+    scripts.sort();
+
+    for script in &scripts {
+        let name: String = script.file_name().unwrap().to_string_lossy().to_string();
+        let expected: String = fs::read_to_string(script.with_extension("out")).unwrap();
+        let mut command: Command = Command::new(env!("CARGO_BIN_EXE_lai"));
+
+        if name.starts_with("check_") {
+            command.arg("check");
+        }
+
+        let output: Output = command.arg(script).output().unwrap();
+        let stdout: String = String::from_utf8_lossy(&output.stdout).to_string();
+
+        // Running a file first prints its tokens and tree, which end with ")".
+        let matches: bool = match name.starts_with("check_") {
+            true => stdout == expected,
+            false => stdout.ends_with(&format!(")\n\n{}", expected)),
+        };
+
+        if !matches {
+            failures.push(format!("{}:\n{}", name, stdout));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}